@@ -0,0 +1,89 @@
+use super::calculator_errors::CalculatorError;
+use crate::cell::{Cell, CellSize};
+use crate::errors::Error;
+
+/// Behaviour of the arithmetic operations when their result does not fit in a cell.
+/// The operations are computed exactly and then the policy decides what to do with the result:
+/// - Wrapping: The result wraps around, like the two's complement arithmetic of real Forth systems.
+/// - Checked: The operation fails with `CalculatorError::Overflow`.
+/// - Saturating: The result is clamped to the smallest or largest value of a cell.
+///
+/// # Examples
+/// ```rust
+///# use rust_forth::calculator::arithmetic_policy::ArithmeticPolicy;
+///# use rust_forth::cell::CellSize;
+/// let result = ArithmeticPolicy::Saturating.apply(40000, CellSize::I16);
+///
+/// assert_eq!(result, Ok(32767));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum ArithmeticPolicy {
+    #[default]
+    Wrapping,
+    Checked,
+    Saturating,
+}
+
+impl ArithmeticPolicy {
+    /// Fits the exact result of an operation in a cell of the given size.
+    pub fn apply(&self, value: i128, cell_size: CellSize) -> Result<Cell, Error> {
+        if cell_size.contains(value) {
+            return Ok(value as Cell);
+        }
+
+        match self {
+            ArithmeticPolicy::Wrapping => Ok(cell_size.wrap(value)),
+            ArithmeticPolicy::Checked => Err(CalculatorError::Overflow.into()),
+            ArithmeticPolicy::Saturating if value < 0 => Ok(cell_size.min()),
+            ArithmeticPolicy::Saturating => Ok(cell_size.max()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_that_fit_in_a_cell_are_kept_by_every_policy() {
+        for policy in [
+            ArithmeticPolicy::Wrapping,
+            ArithmeticPolicy::Checked,
+            ArithmeticPolicy::Saturating,
+        ] {
+            assert_eq!(policy.apply(-32768, CellSize::I16), Ok(-32768));
+            assert_eq!(policy.apply(32767, CellSize::I16), Ok(32767));
+        }
+    }
+
+    #[test]
+    fn wrapping_policy_wraps_around() {
+        let policy = ArithmeticPolicy::Wrapping;
+
+        assert_eq!(policy.apply(32768, CellSize::I16), Ok(-32768));
+        assert_eq!(policy.apply(-32769, CellSize::I16), Ok(32767));
+    }
+
+    #[test]
+    fn checked_policy_reports_an_overflow() {
+        let policy = ArithmeticPolicy::Checked;
+
+        assert_eq!(
+            policy.apply(32768, CellSize::I16),
+            Err(CalculatorError::Overflow.into())
+        );
+        assert_eq!(
+            policy.apply(i64::MIN as i128 - 1, CellSize::I64),
+            Err(CalculatorError::Overflow.into())
+        );
+    }
+
+    #[test]
+    fn saturating_policy_clamps_to_the_limits_of_a_cell() {
+        let policy = ArithmeticPolicy::Saturating;
+
+        assert_eq!(policy.apply(32768, CellSize::I16), Ok(32767));
+        assert_eq!(policy.apply(-40000, CellSize::I16), Ok(-32768));
+        assert_eq!(policy.apply(1 << 40, CellSize::I32), Ok(i32::MAX as Cell));
+    }
+}
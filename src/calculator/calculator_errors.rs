@@ -4,6 +4,7 @@ use std::fmt;
 pub enum CalculatorError {
     DivisionByZero,
     UndifiedOperation,
+    Overflow,
 }
 
 impl fmt::Display for CalculatorError {
@@ -11,6 +12,7 @@ impl fmt::Display for CalculatorError {
         match *self {
             CalculatorError::DivisionByZero => write!(f, "division-by-zero"),
             CalculatorError::UndifiedOperation => write!(f, "undefined-operation"),
+            CalculatorError::Overflow => write!(f, "arithmetic-overflow"),
         }
     }
 }
@@ -0,0 +1,67 @@
+/// Rounding of the quotient of the division operations (`/`, `MOD`, `/MOD`, `*/`, `*/MOD`):
+/// - Symmetric: The quotient is truncated towards zero, and the remainder has the sign of the dividend.
+/// - Floored: The quotient is rounded towards negative infinity, and the remainder has the sign
+///   of the divisor.
+///
+/// Both modes give the same results when the operands have the same sign.
+///
+/// # Examples
+/// ```rust
+///# use rust_forth::calculator::division_mode::DivisionMode;
+/// assert_eq!(DivisionMode::Symmetric.divide(-7, 2), Some((-1, -3)));
+/// assert_eq!(DivisionMode::Floored.divide(-7, 2), Some((1, -4)));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum DivisionMode {
+    #[default]
+    Symmetric,
+    Floored,
+}
+
+impl DivisionMode {
+    /// Divides `dividend` by `divisor`, returning the remainder and the quotient.
+    /// If the divisor is zero, it returns `None`.
+    pub fn divide(&self, dividend: i128, divisor: i128) -> Option<(i128, i128)> {
+        let quotient = dividend.checked_div(divisor)?;
+        let remainder = dividend % divisor;
+
+        match self {
+            DivisionMode::Floored if remainder != 0 && (remainder < 0) != (divisor < 0) => {
+                Some((remainder + divisor, quotient - 1))
+            }
+            _ => Some((remainder, quotient)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symmetric_division_truncates_towards_zero() {
+        let mode = DivisionMode::Symmetric;
+
+        assert_eq!(mode.divide(7, 2), Some((1, 3)));
+        assert_eq!(mode.divide(-7, 2), Some((-1, -3)));
+        assert_eq!(mode.divide(7, -2), Some((1, -3)));
+        assert_eq!(mode.divide(-7, -2), Some((-1, 3)));
+    }
+
+    #[test]
+    fn floored_division_rounds_towards_negative_infinity() {
+        let mode = DivisionMode::Floored;
+
+        assert_eq!(mode.divide(7, 2), Some((1, 3)));
+        assert_eq!(mode.divide(-7, 2), Some((1, -4)));
+        assert_eq!(mode.divide(7, -2), Some((-1, -4)));
+        assert_eq!(mode.divide(-7, -2), Some((-1, 3)));
+        assert_eq!(mode.divide(-6, 2), Some((0, -3)));
+    }
+
+    #[test]
+    fn division_by_zero_has_no_result() {
+        assert_eq!(DivisionMode::Symmetric.divide(1, 0), None);
+        assert_eq!(DivisionMode::Floored.divide(1, 0), None);
+    }
+}
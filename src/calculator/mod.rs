@@ -1,2 +1,4 @@
+pub mod arithmetic_policy;
 pub mod calculator_errors;
+pub mod division_mode;
 pub mod operations;
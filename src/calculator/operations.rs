@@ -1,9 +1,19 @@
+use super::arithmetic_policy::ArithmeticPolicy;
 use super::calculator_errors::CalculatorError;
+use super::division_mode::DivisionMode;
+use crate::cell::{Cell, CellSize};
 use crate::errors::Error;
 
-/// A simple calculator that can perform basic arithmetic operations
+/// A simple calculator that can perform the arithmetic operations of Forth
 /// such as addition, subtraction, multiplication, and division.
-pub struct Calculator {}
+/// Results that do not fit in a cell are handled by its arithmetic policy,
+/// according to the width of the cells. By default they wrap around.
+/// Quotients are rounded according to its division mode. By default they are truncated.
+pub struct Calculator {
+    cell_size: CellSize,
+    policy: ArithmeticPolicy,
+    division: DivisionMode,
+}
 
 impl Default for Calculator {
     fn default() -> Self {
@@ -21,27 +31,166 @@ impl Calculator {
     /// let calculator = Calculator::new();
     /// ```
     pub fn new() -> Self {
-        Calculator {}
+        Calculator {
+            cell_size: CellSize::default(),
+            policy: ArithmeticPolicy::default(),
+            division: DivisionMode::default(),
+        }
+    }
+
+    /// Sets the width of the cells the calculator operates on.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    ///# use rust_forth::calculator::operations::Calculator;
+    ///# use rust_forth::cell::CellSize;
+    /// let calculator = Calculator::new().with_cell_size(CellSize::I32);
+    /// ```
+    pub fn with_cell_size(mut self, cell_size: CellSize) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Sets the policy applied to the results that do not fit in a cell.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    ///# use rust_forth::calculator::operations::Calculator;
+    ///# use rust_forth::calculator::arithmetic_policy::ArithmeticPolicy;
+    /// let calculator = Calculator::new().with_arithmetic_policy(ArithmeticPolicy::Checked);
+    /// ```
+    pub fn with_arithmetic_policy(mut self, policy: ArithmeticPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the rounding of the quotients: symmetric (truncated) or floored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    ///# use rust_forth::calculator::operations::Calculator;
+    ///# use rust_forth::calculator::division_mode::DivisionMode;
+    /// let calculator = Calculator::new().with_division_mode(DivisionMode::Floored);
+    ///
+    /// assert_eq!(calculator.calculate(-7, 2, "/"), Ok(-4));
+    /// ```
+    pub fn with_division_mode(mut self, division: DivisionMode) -> Self {
+        self.division = division;
+        self
     }
 
-    fn add(&self, n1: i16, n2: i16) -> i16 {
-        n1 + n2
+    /// Fits the exact result of an operation in a cell, following the arithmetic policy.
+    fn fit(&self, value: i128) -> Result<Cell, Error> {
+        self.policy.apply(value, self.cell_size)
     }
 
-    fn subtract(&self, n1: i16, n2: i16) -> i16 {
-        n1 - n2
+    fn add(&self, n1: Cell, n2: Cell) -> Result<Cell, Error> {
+        self.fit(n1 as i128 + n2 as i128)
     }
 
-    fn multiply(&self, n1: i16, n2: i16) -> i16 {
-        n1 * n2
+    fn subtract(&self, n1: Cell, n2: Cell) -> Result<Cell, Error> {
+        self.fit(n1 as i128 - n2 as i128)
+    }
+
+    fn multiply(&self, n1: Cell, n2: Cell) -> Result<Cell, Error> {
+        self.fit(n1 as i128 * n2 as i128)
     }
 
     /// Divides two numbers and returns the result.
     /// If the second number is zero, it returns an error.
-    fn divide(&self, n1: i16, n2: i16) -> Result<i16, Error> {
-        match n2 {
-            0 => Err(CalculatorError::DivisionByZero.into()),
-            _ => Ok(n1 / n2),
+    /// The only quotient that does not fit in a cell is the one of the smallest value by -1.
+    fn divide(&self, n1: Cell, n2: Cell) -> Result<Cell, Error> {
+        let (_, quotient) = self.divide_with_remainder(n1 as i128, n2 as i128)?;
+        Ok(quotient)
+    }
+
+    /// Divides two numbers, returning the remainder and the quotient.
+    /// The dividend can be wider than a cell, like the intermediate product of `*/`.
+    /// If the divisor is zero, it returns an error.
+    fn divide_with_remainder(&self, dividend: i128, divisor: i128) -> Result<(Cell, Cell), Error> {
+        let (remainder, quotient) = self
+            .division
+            .divide(dividend, divisor)
+            .ok_or(CalculatorError::DivisionByZero)?;
+        Ok((self.fit(remainder)?, self.fit(quotient)?))
+    }
+
+    /// Returns the number of operands taken from the stack by an operation,
+    /// or `None` if the operation is not recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    ///# use rust_forth::calculator::operations::Calculator;
+    /// let calculator = Calculator::new();
+    ///
+    /// assert_eq!(calculator.arity("negate"), Some(1));
+    /// assert_eq!(calculator.arity("*/mod"), Some(3));
+    /// assert_eq!(calculator.arity("%"), None);
+    /// ```
+    pub fn arity(&self, operation: &str) -> Option<usize> {
+        match operation {
+            "negate" | "abs" | "1+" | "1-" | "2*" | "2/" => Some(1),
+            "+" | "-" | "*" | "/" | "mod" | "/mod" | "min" | "max" => Some(2),
+            "*/" | "*/mod" => Some(3),
+            _ => None,
+        }
+    }
+
+    /// Performs the specified operation on its operands, given in the order they were pushed.
+    /// Returns the results in the order they must be pushed.
+    ///
+    /// The operations supported, besides the ones of `calculate`, are:
+    /// - "negate", "abs", "1+", "1-", "2*" and "2/", which take one operand.
+    /// - "mod", "min" and "max", which take two operands.
+    /// - "/mod", which takes two operands and returns the remainder and the quotient.
+    /// - "*/", which multiplies the first two operands and divides the product by the third one.
+    /// - "*/mod", like "*/" but returning the remainder and the quotient.
+    ///
+    /// The product of "*/" and "*/mod" is kept with double width, so it never overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    ///# use rust_forth::calculator::operations::Calculator;
+    /// let calculator = Calculator::new();
+    ///
+    /// assert_eq!(calculator.execute(&[7, 2], "/mod"), Ok(vec![1, 3]));
+    /// assert_eq!(calculator.execute(&[30000, 3, 4], "*/"), Ok(vec![22500]));
+    /// ```
+    pub fn execute(&self, operands: &[Cell], operation: &str) -> Result<Vec<Cell>, Error> {
+        match (operation, operands) {
+            ("negate", &[n]) => Ok(vec![self.fit(-(n as i128))?]),
+            ("abs", &[n]) => Ok(vec![self.fit((n as i128).abs())?]),
+            ("1+", &[n]) => Ok(vec![self.add(n, 1)?]),
+            ("1-", &[n]) => Ok(vec![self.subtract(n, 1)?]),
+            ("2*", &[n]) => Ok(vec![self.multiply(n, 2)?]),
+            ("2/", &[n]) => Ok(vec![n >> 1]),
+            ("mod", &[n1, n2]) => {
+                let (remainder, _) = self.divide_with_remainder(n1 as i128, n2 as i128)?;
+                Ok(vec![remainder])
+            }
+            ("/mod", &[n1, n2]) => {
+                let (remainder, quotient) = self.divide_with_remainder(n1 as i128, n2 as i128)?;
+                Ok(vec![remainder, quotient])
+            }
+            ("min", &[n1, n2]) => Ok(vec![n1.min(n2)]),
+            ("max", &[n1, n2]) => Ok(vec![n1.max(n2)]),
+            ("*/", &[n1, n2, n3]) => {
+                let (_, quotient) =
+                    self.divide_with_remainder(n1 as i128 * n2 as i128, n3 as i128)?;
+                Ok(vec![quotient])
+            }
+            ("*/mod", &[n1, n2, n3]) => {
+                let (remainder, quotient) =
+                    self.divide_with_remainder(n1 as i128 * n2 as i128, n3 as i128)?;
+                Ok(vec![remainder, quotient])
+            }
+            (_, &[n1, n2]) => Ok(vec![self.calculate(n1, n2, operation)?]),
+            _ => Err(CalculatorError::UndifiedOperation.into()),
         }
     }
 
@@ -57,13 +206,14 @@ impl Calculator {
     ///   - "*" for multiplication
     ///   - "/" for division
     ///
-    /// Returns the result of the operation as an `i16` value.
-    /// If the operation is not recognized, it returns an error.   
-    pub fn calculate(&self, n1: i16, n2: i16, operation: &str) -> Result<i16, Error> {
+    /// Returns the result of the operation as a `Cell` value.
+    /// If the operation is not recognized, or the result overflows with the checked policy,
+    /// it returns an error.   
+    pub fn calculate(&self, n1: Cell, n2: Cell, operation: &str) -> Result<Cell, Error> {
         match operation {
-            "+" => Ok(self.add(n1, n2)),
-            "-" => Ok(self.subtract(n1, n2)),
-            "*" => Ok(self.multiply(n1, n2)),
+            "+" => self.add(n1, n2),
+            "-" => self.subtract(n1, n2),
+            "*" => self.multiply(n1, n2),
             "/" => self.divide(n1, n2),
             _ => Err(CalculatorError::UndifiedOperation.into()),
         }
@@ -71,15 +221,21 @@ impl Calculator {
 }
 
 mod tests {
+    #[allow(unused_imports)]
+    use crate::calculator::arithmetic_policy::ArithmeticPolicy;
+    #[allow(unused_imports)]
+    use crate::calculator::division_mode::DivisionMode;
     #[allow(unused_imports)]
     use crate::calculator::{calculator_errors::CalculatorError, operations::Calculator};
+    #[allow(unused_imports)]
+    use crate::cell::CellSize;
 
     #[test]
     fn a_calculator_can_add_correctly() {
         let calculator = Calculator::new();
         let n1 = 2;
         let n2 = 4;
-        let expected_result = 6;
+        let expected_result = Ok(6);
 
         let result = calculator.add(n1, n2);
 
@@ -91,7 +247,7 @@ mod tests {
         let calculator = Calculator::new();
         let n1 = 4;
         let n2 = 2;
-        let expected_result = 2;
+        let expected_result = Ok(2);
 
         let result = calculator.subtract(n1, n2);
 
@@ -103,7 +259,7 @@ mod tests {
         let calculator = Calculator::new();
         let n1 = 4;
         let n2 = 2;
-        let expected_result = 8;
+        let expected_result = Ok(8);
 
         let result = calculator.multiply(n1, n2);
 
@@ -154,4 +310,148 @@ mod tests {
         assert_eq!(multiplication_result, expected_multiplication_result);
         assert_eq!(division_result, expected_division_result);
     }
+
+    #[test]
+    fn results_wrap_around_according_to_the_cell_size() {
+        let calculator = Calculator::new();
+        let wide_calculator = Calculator::new().with_cell_size(CellSize::I32);
+
+        assert_eq!(calculator.calculate(32767, 1, "+"), Ok(-32768));
+        assert_eq!(calculator.calculate(-32768, -1, "/"), Ok(-32768));
+        assert_eq!(wide_calculator.calculate(32767, 1, "+"), Ok(32768));
+        assert_eq!(wide_calculator.calculate(1000, 1000, "*"), Ok(1000000));
+    }
+
+    #[test]
+    fn wrapping_policy_wraps_every_edge_case() {
+        let calculator = Calculator::new().with_arithmetic_policy(ArithmeticPolicy::Wrapping);
+
+        assert_eq!(calculator.calculate(32767, 1, "+"), Ok(-32768));
+        assert_eq!(calculator.calculate(-32768, 1, "-"), Ok(32767));
+        assert_eq!(calculator.calculate(256, 256, "*"), Ok(0));
+        assert_eq!(calculator.calculate(-32768, -1, "*"), Ok(-32768));
+        assert_eq!(calculator.calculate(-32768, -1, "/"), Ok(-32768));
+    }
+
+    #[test]
+    fn checked_policy_reports_every_edge_case() {
+        let calculator = Calculator::new().with_arithmetic_policy(ArithmeticPolicy::Checked);
+        let overflow = Err(CalculatorError::Overflow.into());
+
+        assert_eq!(calculator.calculate(32767, 1, "+"), overflow);
+        assert_eq!(calculator.calculate(-32768, 1, "-"), overflow);
+        assert_eq!(calculator.calculate(256, 256, "*"), overflow);
+        assert_eq!(calculator.calculate(-32768, -1, "*"), overflow);
+        assert_eq!(calculator.calculate(-32768, -1, "/"), overflow);
+        assert_eq!(calculator.calculate(-32767, -1, "/"), Ok(32767));
+    }
+
+    #[test]
+    fn saturating_policy_clamps_every_edge_case() {
+        let calculator = Calculator::new().with_arithmetic_policy(ArithmeticPolicy::Saturating);
+
+        assert_eq!(calculator.calculate(32767, 1, "+"), Ok(32767));
+        assert_eq!(calculator.calculate(-32768, 1, "-"), Ok(-32768));
+        assert_eq!(calculator.calculate(256, -256, "*"), Ok(-32768));
+        assert_eq!(calculator.calculate(-32768, -1, "*"), Ok(32767));
+        assert_eq!(calculator.calculate(-32768, -1, "/"), Ok(32767));
+    }
+
+    #[test]
+    fn policies_apply_to_the_width_of_the_cells() {
+        let calculator = Calculator::new()
+            .with_cell_size(CellSize::I64)
+            .with_arithmetic_policy(ArithmeticPolicy::Checked);
+
+        assert_eq!(calculator.calculate(32767, 1, "+"), Ok(32768));
+        assert_eq!(
+            calculator.calculate(i64::MIN, -1, "/"),
+            Err(CalculatorError::Overflow.into())
+        );
+        assert_eq!(
+            calculator.calculate(i64::MAX, 1, "+"),
+            Err(CalculatorError::Overflow.into())
+        );
+    }
+
+    #[test]
+    fn a_calculator_can_execute_the_unary_operations() {
+        let calculator = Calculator::new();
+
+        assert_eq!(calculator.execute(&[5], "negate"), Ok(vec![-5]));
+        assert_eq!(calculator.execute(&[-5], "abs"), Ok(vec![5]));
+        assert_eq!(calculator.execute(&[5], "1+"), Ok(vec![6]));
+        assert_eq!(calculator.execute(&[5], "1-"), Ok(vec![4]));
+        assert_eq!(calculator.execute(&[5], "2*"), Ok(vec![10]));
+        assert_eq!(calculator.execute(&[-5], "2/"), Ok(vec![-3]));
+    }
+
+    #[test]
+    fn a_calculator_can_execute_mod_min_and_max() {
+        let calculator = Calculator::new();
+
+        assert_eq!(calculator.execute(&[7, 3], "mod"), Ok(vec![1]));
+        assert_eq!(calculator.execute(&[7, 3], "/mod"), Ok(vec![1, 2]));
+        assert_eq!(calculator.execute(&[7, -3], "min"), Ok(vec![-3]));
+        assert_eq!(calculator.execute(&[7, -3], "max"), Ok(vec![7]));
+        assert_eq!(
+            calculator.execute(&[7, 0], "mod"),
+            Err(CalculatorError::DivisionByZero.into())
+        );
+    }
+
+    #[test]
+    fn star_slash_keeps_a_double_width_intermediate_product() {
+        let calculator = Calculator::new().with_arithmetic_policy(ArithmeticPolicy::Checked);
+
+        assert_eq!(
+            calculator.execute(&[20000, 30000, 25000], "*/"),
+            Ok(vec![24000])
+        );
+        assert_eq!(
+            calculator.execute(&[30000, 3, 7], "*/mod"),
+            Ok(vec![1, 12857])
+        );
+        assert_eq!(
+            calculator.execute(&[30000, 30000, 1], "*/"),
+            Err(CalculatorError::Overflow.into())
+        );
+    }
+
+    #[test]
+    fn sixty_four_bit_star_slash_does_not_overflow() {
+        let calculator = Calculator::new()
+            .with_cell_size(CellSize::I64)
+            .with_arithmetic_policy(ArithmeticPolicy::Checked);
+
+        assert_eq!(
+            calculator.execute(&[i64::MAX, i64::MAX, i64::MAX], "*/"),
+            Ok(vec![i64::MAX])
+        );
+    }
+
+    #[test]
+    fn floored_division_rounds_towards_negative_infinity() {
+        let symmetric = Calculator::new();
+        let floored = Calculator::new().with_division_mode(DivisionMode::Floored);
+
+        assert_eq!(symmetric.execute(&[-7, 2], "/mod"), Ok(vec![-1, -3]));
+        assert_eq!(floored.execute(&[-7, 2], "/mod"), Ok(vec![1, -4]));
+        assert_eq!(symmetric.execute(&[-7, 2, 1], "*/"), Ok(vec![-14]));
+        assert_eq!(floored.execute(&[-7, 1, 2], "*/"), Ok(vec![-4]));
+        assert_eq!(floored.calculate(7, -2, "/"), Ok(-4));
+    }
+
+    #[test]
+    fn negating_the_smallest_cell_follows_the_policy() {
+        let wrapping = Calculator::new();
+        let checked = Calculator::new().with_arithmetic_policy(ArithmeticPolicy::Checked);
+
+        assert_eq!(wrapping.execute(&[-32768], "negate"), Ok(vec![-32768]));
+        assert_eq!(wrapping.execute(&[-32768], "abs"), Ok(vec![-32768]));
+        assert_eq!(
+            checked.execute(&[-32768], "abs"),
+            Err(CalculatorError::Overflow.into())
+        );
+    }
 }
@@ -0,0 +1,339 @@
+/// Value held by a cell of the stacks and the data space.
+/// Cells are stored with the widest supported width, and the operations that produce them
+/// keep them in the range of the `CellSize` selected for the interpreter.
+pub type Cell = i64;
+
+/// Width of the cells of the interpreter.
+/// The default width is 16 bits, as in the original interpreter.
+///
+/// # Examples
+/// ```
+///# use rust_forth::cell::CellSize;
+/// let cell_size = CellSize::I32;
+///
+/// assert_eq!(cell_size.bytes(), 4);
+/// assert_eq!(cell_size.wrap(i32::MAX as i128 + 1), i32::MIN as i64);
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum CellSize {
+    #[default]
+    I16,
+    I32,
+    I64,
+}
+
+impl CellSize {
+    /// Number of bytes that a cell occupies.
+    pub fn bytes(&self) -> usize {
+        match self {
+            CellSize::I16 => 2,
+            CellSize::I32 => 4,
+            CellSize::I64 => 8,
+        }
+    }
+
+    /// Number of bits of a cell.
+    pub fn bits(&self) -> u32 {
+        self.bytes() as u32 * 8
+    }
+
+    /// Reads a value as an unsigned number of the width of a cell.
+    pub fn unsigned(&self, value: Cell) -> u128 {
+        value as u128 & (u128::MAX >> (128 - self.bits()))
+    }
+
+    /// Joins two cells into an unsigned double-cell number, the high cell being the most significant.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::cell::CellSize;
+    /// assert_eq!(CellSize::I16.join_double(-1, 1), 0x1FFFF);
+    /// ```
+    pub fn join_double(&self, low: Cell, high: Cell) -> u128 {
+        (self.unsigned(high) << self.bits()) | self.unsigned(low)
+    }
+
+    /// Splits an unsigned double-cell number into its low and high cells.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::cell::CellSize;
+    /// assert_eq!(CellSize::I16.split_double(0x1FFFF), (-1, 1));
+    /// ```
+    pub fn split_double(&self, value: u128) -> (Cell, Cell) {
+        let low = self.wrap(value as i128);
+        let high = self.wrap((value >> self.bits()) as i128);
+        (low, high)
+    }
+
+    /// Smallest value that fits in a cell.
+    pub fn min(&self) -> Cell {
+        match self {
+            CellSize::I16 => i16::MIN as Cell,
+            CellSize::I32 => i32::MIN as Cell,
+            CellSize::I64 => i64::MIN,
+        }
+    }
+
+    /// Largest value that fits in a cell.
+    pub fn max(&self) -> Cell {
+        match self {
+            CellSize::I16 => i16::MAX as Cell,
+            CellSize::I32 => i32::MAX as Cell,
+            CellSize::I64 => i64::MAX,
+        }
+    }
+
+    /// Checks if a value fits in a cell.
+    pub fn contains(&self, value: i128) -> bool {
+        (self.min() as i128..=self.max() as i128).contains(&value)
+    }
+
+    /// Truncates a value to the width of a cell, wrapping around like two's complement hardware.
+    pub fn wrap(&self, value: i128) -> Cell {
+        match self {
+            CellSize::I16 => value as i16 as Cell,
+            CellSize::I32 => value as i32 as Cell,
+            CellSize::I64 => value as i64,
+        }
+    }
+
+    /// Parses a decimal number literal, if it fits in a cell.
+    pub fn parse(&self, token: &str) -> Option<Cell> {
+        self.parse_in_base(token, DECIMAL_BASE)
+    }
+
+    /// Parses a number literal in the given base, if it fits in a cell.
+    /// The literal may override the base with a prefix: `#` for decimal, `$` for hexadecimal
+    /// and `%` for binary. A character literal such as `'A'` is parsed as its code.
+    ///
+    /// Bases that are not between 2 and 36 fall back to decimal.
+    /// Decimal literals must fit in a signed cell. Literals in other bases may also be
+    /// written as unsigned numbers, like the mask `$FFFF`, which is `-1` with 16-bit cells.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::cell::CellSize;
+    /// let cell_size = CellSize::I16;
+    ///
+    /// assert_eq!(cell_size.parse_in_base("ff", 16), Some(255));
+    /// assert_eq!(cell_size.parse_in_base("$FFFF", 10), Some(-1));
+    /// assert_eq!(cell_size.parse_in_base("%-101", 10), Some(-5));
+    /// assert_eq!(cell_size.parse_in_base("'A'", 10), Some(65));
+    /// assert_eq!(cell_size.parse_in_base("12", 2), None);
+    /// ```
+    pub fn parse_in_base(&self, token: &str, base: u32) -> Option<Cell> {
+        if let Some(character) = char_literal(token) {
+            return Some(self.wrap(character as i128));
+        }
+        let (base, negative, digits) = split_literal(token, base)?;
+        let magnitude = digits.chars().try_fold(0i128, |value, digit| {
+            let digit = digit.to_digit(base)? as i128;
+            value.checked_mul(base as i128)?.checked_add(digit)
+        })?;
+        let value = if negative { -magnitude } else { magnitude };
+
+        if self.contains(value) {
+            Some(value as Cell)
+        } else if base != DECIMAL_BASE && !negative && value <= self.unsigned(-1) as i128 {
+            Some(self.wrap(value))
+        } else {
+            None
+        }
+    }
+}
+
+/// Base of the numbers after `DECIMAL`, the default one.
+pub const DECIMAL_BASE: u32 = 10;
+/// Base of the numbers after `HEX`.
+pub const HEX_BASE: u32 = 16;
+/// Base of the numbers after `BINARY`.
+pub const BINARY_BASE: u32 = 2;
+/// Largest base in which numbers can be read and printed, using the digits `0-9` and `A-Z`.
+pub const MAX_BASE: u32 = 36;
+
+/// Converts a value, such as the content of `BASE`, into a numeric base.
+/// Values that are not between 2 and 36 fall back to decimal.
+///
+/// # Examples
+/// ```
+///# use rust_forth::cell::numeric_base;
+/// assert_eq!(numeric_base(16), 16);
+/// assert_eq!(numeric_base(1), 10);
+/// ```
+pub fn numeric_base(value: Cell) -> u32 {
+    u32::try_from(value)
+        .ok()
+        .filter(|base| (BINARY_BASE..=MAX_BASE).contains(base))
+        .unwrap_or(DECIMAL_BASE)
+}
+
+/// Checks if a token is written as a number literal in the given base,
+/// regardless of whether it fits in a cell.
+///
+/// # Examples
+/// ```
+///# use rust_forth::cell::is_literal;
+/// assert!(is_literal("-ff", 16));
+/// assert!(is_literal("$ff", 10));
+/// assert!(!is_literal("ff", 10));
+/// assert!(!is_literal("-", 10));
+/// ```
+pub fn is_literal(token: &str, base: u32) -> bool {
+    char_literal(token).is_some()
+        || split_literal(token, base)
+            .is_some_and(|(base, _, digits)| digits.chars().all(|digit| digit.is_digit(base)))
+}
+
+/// Checks if a number literal is read the same in every base,
+/// as the characters and the literals with a base prefix are.
+///
+/// # Examples
+/// ```
+///# use rust_forth::cell::is_base_independent;
+/// assert!(is_base_independent("$ff"));
+/// assert!(is_base_independent("'A'"));
+/// assert!(!is_base_independent("10"));
+/// ```
+pub fn is_base_independent(token: &str) -> bool {
+    char_literal(token).is_some() || token.starts_with(['#', '$', '%'])
+}
+
+/// Formats a number in the given base, with uppercase letters for the digits above 9.
+/// Like when parsing, bases that are not between 2 and 36 fall back to decimal.
+///
+/// # Examples
+/// ```
+///# use rust_forth::cell::format_in_base;
+/// assert_eq!(format_in_base(255, 16), "FF");
+/// assert_eq!(format_in_base(-5, 2), "-101");
+/// ```
+pub fn format_in_base(value: i128, base: u32) -> String {
+    let base = numeric_base(base as Cell);
+    let mut magnitude = value.unsigned_abs();
+    let mut digits = Vec::new();
+    loop {
+        let digit = (magnitude % base as u128) as u32;
+        digits.push(
+            char::from_digit(digit, base)
+                .unwrap_or('?')
+                .to_ascii_uppercase(),
+        );
+        magnitude /= base as u128;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if value < 0 {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
+
+/// Reads a character literal such as `'A'`.
+fn char_literal(token: &str) -> Option<char> {
+    let mut chars = token.strip_prefix('\'')?.strip_suffix('\'')?.chars();
+    let character = chars.next()?;
+    chars.next().is_none().then_some(character)
+}
+
+/// Splits a number literal into its base, which may be overridden by a prefix,
+/// its sign and its digits. The digits are not checked.
+fn split_literal(token: &str, base: u32) -> Option<(u32, bool, &str)> {
+    let (base, token) = match token.chars().next()? {
+        '#' => (DECIMAL_BASE, &token[1..]),
+        '$' => (HEX_BASE, &token[1..]),
+        '%' => (BINARY_BASE, &token[1..]),
+        _ => (numeric_base(base as Cell), token),
+    };
+    let (negative, digits) = match token.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, token),
+    };
+    (!digits.is_empty()).then_some((base, negative, digits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_cell_numbers_can_be_joined_and_split() {
+        for cell_size in [CellSize::I16, CellSize::I32, CellSize::I64] {
+            let value = cell_size.join_double(-2, 3);
+
+            assert_eq!(cell_size.split_double(value), (-2, 3));
+        }
+        assert_eq!(CellSize::I64.join_double(-1, -1), u128::MAX);
+    }
+
+    #[test]
+    fn the_default_cell_size_is_sixteen_bits() {
+        let cell_size = CellSize::default();
+
+        assert_eq!(cell_size, CellSize::I16);
+        assert_eq!(cell_size.bytes(), 2);
+    }
+
+    #[test]
+    fn values_are_wrapped_to_the_width_of_the_cell() {
+        assert_eq!(CellSize::I16.wrap(32768), -32768);
+        assert_eq!(CellSize::I32.wrap(32768), 32768);
+        assert_eq!(CellSize::I32.wrap(i32::MIN as i128 - 1), i32::MAX as Cell);
+        assert_eq!(CellSize::I64.wrap(i64::MAX as i128 + 1), i64::MIN);
+    }
+
+    #[test]
+    fn values_can_be_read_as_unsigned_numbers() {
+        assert_eq!(CellSize::I16.unsigned(-1), 65535);
+        assert_eq!(CellSize::I32.unsigned(-2), u32::MAX as u128 - 1);
+        assert_eq!(CellSize::I64.unsigned(7), 7);
+    }
+
+    #[test]
+    fn only_literals_that_fit_in_a_cell_are_parsed() {
+        assert_eq!(CellSize::I16.parse("-32768"), Some(-32768));
+        assert_eq!(CellSize::I16.parse("40000"), None);
+        assert_eq!(CellSize::I32.parse("40000"), Some(40000));
+        assert_eq!(CellSize::I64.parse("9223372036854775807"), Some(i64::MAX));
+        assert_eq!(CellSize::I64.parse("9223372036854775808"), None);
+    }
+
+    #[test]
+    fn literals_can_be_parsed_in_other_bases() {
+        assert_eq!(
+            CellSize::I16.parse_in_base("7fff", HEX_BASE),
+            Some(i16::MAX as Cell)
+        );
+        assert_eq!(CellSize::I16.parse_in_base("ffff", HEX_BASE), Some(-1));
+        assert_eq!(CellSize::I16.parse_in_base("10000", HEX_BASE), None);
+        assert_eq!(CellSize::I16.parse_in_base("-1010", BINARY_BASE), Some(-10));
+        assert_eq!(CellSize::I16.parse_in_base("#10", HEX_BASE), Some(10));
+        assert_eq!(CellSize::I16.parse_in_base("65535", DECIMAL_BASE), None);
+        assert_eq!(
+            CellSize::I64.parse_in_base("$ffffffffffffffff", 10),
+            Some(-1)
+        );
+    }
+
+    #[test]
+    fn only_well_formed_literals_are_recognized() {
+        assert!(is_literal("'a'", DECIMAL_BASE));
+        assert!(is_literal("%101", HEX_BASE));
+        assert!(!is_literal("%102", DECIMAL_BASE));
+        assert!(!is_literal("$", DECIMAL_BASE));
+        assert!(!is_literal("'ab'", DECIMAL_BASE));
+        assert!(!is_literal("dup", HEX_BASE));
+    }
+
+    #[test]
+    fn numbers_are_formatted_in_any_base() {
+        assert_eq!(format_in_base(0, HEX_BASE), "0");
+        assert_eq!(format_in_base(-255, HEX_BASE), "-FF");
+        assert_eq!(format_in_base(35, MAX_BASE), "Z");
+        assert_eq!(
+            format_in_base(i64::MIN as i128, DECIMAL_BASE),
+            i64::MIN.to_string()
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::calculator::calculator_errors::CalculatorError;
 use crate::forth::forth_errors::ForthError;
+use crate::forth::span::Span;
 use crate::stack::stack_errors::StackError;
 use std::fmt;
 
@@ -9,7 +10,146 @@ pub enum Error {
     CalculatorError(CalculatorError),
     ForthError(ForthError),
     InvalidStackSize,
+    InvalidDataSpaceSize,
+    InvalidMaxCallDepth,
+    InvalidErrorFormat,
+    InvalidCellSize,
+    InvalidArithmeticPolicy,
+    InvalidDivisionMode,
+    InvalidBooleanMode,
+    InvalidOutputMode,
+    InvalidCaseSensitivity,
     MissingPathError,
+    UnknownOption(String),
+    Located(Box<Error>, Box<Location>),
+    Traceback(Box<Error>, Box<Traceback>),
+}
+
+/// Place of the source code where an error was raised.
+/// `word` is the word that was being executed: the name of the user-defined word
+/// that contains the failing instruction, or the failing token itself.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Location {
+    pub source: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub word: String,
+}
+
+impl Location {
+    /// Creates a location from the span of a token, for an error raised inside `word`.
+    pub fn new(span: &Span, word: impl Into<String>) -> Self {
+        Location {
+            source: None,
+            line: span.line,
+            column: span.column,
+            word: word.into(),
+        }
+    }
+}
+
+/// Chain of user-defined words that were being executed when an error was raised,
+/// from the innermost to the outermost, and the line where the outermost was called.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Traceback {
+    pub words: Vec<String>,
+    pub line: Option<usize>,
+}
+
+/// Format used to report errors.
+/// `Terse` prints only the message (e.g. `stack-underflow`), while `Rich` adds
+/// the location of the error (e.g. `input.fth:12:5: stack-underflow in 'double'`)
+/// and its traceback.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum ErrorFormat {
+    #[default]
+    Terse,
+    Rich,
+}
+
+impl ErrorFormat {
+    /// Adapts an error to the format: terse errors lose their location.
+    pub fn apply(&self, error: Error) -> Error {
+        match self {
+            ErrorFormat::Terse => error.into_terse(),
+            ErrorFormat::Rich => error,
+        }
+    }
+}
+
+impl Error {
+    /// Attaches a location to the error, unless it already has one.
+    pub fn locate(self, location: Location) -> Error {
+        match self {
+            Error::Located(..) => self,
+            Error::Traceback(error, traceback) => {
+                Error::Traceback(Box::new(error.locate(location)), traceback)
+            }
+            error => Error::Located(Box::new(error), Box::new(location)),
+        }
+    }
+
+    /// Sets the name of the source code where the error was raised, if it has a location.
+    pub fn with_source(self, source: Option<&str>) -> Error {
+        match self {
+            Error::Located(error, mut location) => {
+                if location.source.is_none() {
+                    location.source = source.map(str::to_string);
+                }
+                Error::Located(error, location)
+            }
+            Error::Traceback(error, traceback) => {
+                Error::Traceback(Box::new(error.with_source(source)), traceback)
+            }
+            error => error,
+        }
+    }
+
+    /// Attaches the chain of words being executed to the error, unless it already has one.
+    /// Words go from the innermost to the outermost.
+    pub fn with_traceback(self, words: Vec<String>) -> Error {
+        match self {
+            Error::Traceback(..) => self,
+            error => Error::Traceback(Box::new(error), Box::new(Traceback { words, line: None })),
+        }
+    }
+
+    /// Sets the line where the outermost word of the traceback was called, if it has a traceback.
+    pub fn called_at(self, line: usize) -> Error {
+        match self {
+            Error::Traceback(error, mut traceback) => {
+                traceback.line = traceback.line.or(Some(line));
+                Error::Traceback(error, traceback)
+            }
+            error => error,
+        }
+    }
+
+    /// Returns the error without its location and traceback.
+    pub fn into_terse(self) -> Error {
+        match self {
+            Error::Located(error, _) => error.into_terse(),
+            Error::Traceback(error, _) => error.into_terse(),
+            error => error,
+        }
+    }
+
+    /// Returns the location of the error, if any.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Error::Located(_, location) => Some(location),
+            Error::Traceback(error, _) => error.location(),
+            _ => None,
+        }
+    }
+
+    /// Returns the traceback of the error, if any.
+    pub fn traceback(&self) -> Option<&Traceback> {
+        match self {
+            Error::Traceback(_, traceback) => Some(traceback),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -19,8 +159,55 @@ impl fmt::Display for Error {
             Error::CalculatorError(ref error) => write!(f, "{}", error),
             Error::ForthError(ref error) => write!(f, "{}", error),
             Error::InvalidStackSize => write!(f, "invalid stack size"),
+            Error::InvalidDataSpaceSize => write!(f, "invalid data space size"),
+            Error::InvalidMaxCallDepth => write!(f, "invalid max call depth"),
+            Error::InvalidErrorFormat => write!(f, "invalid error format"),
+            Error::InvalidCellSize => write!(f, "invalid cell size"),
+            Error::InvalidArithmeticPolicy => write!(f, "invalid arithmetic policy"),
+            Error::InvalidDivisionMode => write!(f, "invalid division mode"),
+            Error::InvalidBooleanMode => write!(f, "invalid boolean mode"),
+            Error::InvalidOutputMode => write!(f, "invalid output mode"),
+            Error::InvalidCaseSensitivity => write!(f, "invalid case sensitivity"),
             Error::MissingPathError => write!(f, "path to file not received"),
+            Error::UnknownOption(ref option) => write!(f, "unknown option {}", option),
+            Error::Located(ref error, ref location) => match **error {
+                Error::ForthError(ForthError::UnknownWord(Some(ref unknown))) => {
+                    write!(f, "{}{} ? in '{}'", location, unknown.token, location.word)
+                }
+                _ => write!(f, "{}{} in '{}'", location, error, location.word),
+            },
+            Error::Traceback(ref error, ref traceback) => write!(f, "{}{}", error, traceback),
+        }
+    }
+}
+
+impl fmt::Display for Traceback {
+    /// Consecutive calls to the same word (e.g. recursion) are shown once, with their count.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words = self.words.iter().peekable();
+        while let Some(word) = words.next() {
+            let mut calls = 1;
+            while words.next_if(|next| *next == word).is_some() {
+                calls += 1;
+            }
+            write!(f, "\n  in {}", word)?;
+            if calls > 1 {
+                write!(f, " ({} calls)", calls)?;
+            }
+        }
+        if let Some(line) = self.line {
+            write!(f, "\n  at line {}", line)?;
         }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref source) = self.source {
+            write!(f, "{}:", source)?;
+        }
+        write!(f, "{}:{}: ", self.line, self.column)
     }
 }
 
@@ -43,3 +230,63 @@ impl From<ForthError> for Error {
         Error::ForthError(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn located_error_shows_source_line_column_and_word() {
+        let location = Location::new(&Span::new(12, 5, "double"), "double");
+        let error = Error::from(StackError::Underflow)
+            .locate(location)
+            .with_source(Some("input.fth"));
+        let expected_result = "input.fth:12:5: stack-underflow in 'double'";
+
+        assert_eq!(error.to_string(), expected_result);
+    }
+
+    #[test]
+    fn terse_format_keeps_only_the_message() {
+        let location = Location::new(&Span::new(1, 3, "+"), "+");
+        let error = Error::from(StackError::Underflow).locate(location);
+
+        let result = ErrorFormat::Terse.apply(error);
+
+        assert_eq!(result, Error::StackError(StackError::Underflow));
+        assert_eq!(result.to_string(), "stack-underflow");
+    }
+
+    #[test]
+    fn locate_keeps_the_innermost_location() {
+        let inner = Location::new(&Span::new(1, 10, "+"), "square");
+        let outer = Location::new(&Span::new(2, 1, "square"), "square");
+
+        let error = Error::from(StackError::Underflow)
+            .locate(inner.clone())
+            .locate(outer);
+
+        assert_eq!(error.location(), Some(&inner));
+    }
+
+    #[test]
+    fn traceback_shows_the_chain_of_words_and_the_line() {
+        let words = vec!["square".to_string(), "hypot".to_string()];
+        let error = Error::from(StackError::Underflow)
+            .with_traceback(words)
+            .called_at(7);
+        let expected_result = "stack-underflow\n  in square\n  in hypot\n  at line 7";
+
+        assert_eq!(error.to_string(), expected_result);
+        assert_eq!(error.into_terse(), Error::StackError(StackError::Underflow));
+    }
+
+    #[test]
+    fn traceback_groups_recursive_calls() {
+        let words = vec!["fact".to_string(), "fact".to_string(), "main".to_string()];
+        let error = Error::from(StackError::Underflow).with_traceback(words);
+        let expected_result = "stack-underflow\n  in fact (2 calls)\n  in main";
+
+        assert_eq!(error.to_string(), expected_result);
+    }
+}
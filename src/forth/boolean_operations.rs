@@ -1,34 +1,75 @@
+use crate::cell::{Cell, CellSize};
+
 /// Constants for boolean operations
-/// FORTH_TRUE and FORTH_FALSE are represented as i16 values.
+/// FORTH_TRUE and FORTH_FALSE are represented as cell values.
 /// FORTH_TRUE is -1 and FORTH_FALSE is 0.
 /// This is a common convention in many programming languages.
-/// The use of i16 allows for a wider range of values, but in this case,
+/// A cell allows for a wider range of values, but in this case,
 /// we are only using -1 and 0 to represent FORTH_TRUE and FORTH_FALSE respectively.
-pub const FORTH_TRUE: i16 = -1;
-pub const FORTH_FALSE: i16 = 0;
+pub const FORTH_TRUE: Cell = -1;
+pub const FORTH_FALSE: Cell = 0;
 
 /// Constants for boolean operations
 pub const AND: BooleanOperation = BooleanOperation::And;
 pub const OR: BooleanOperation = BooleanOperation::Or;
 pub const NOT: BooleanOperation = BooleanOperation::Not;
+pub const XOR: BooleanOperation = BooleanOperation::Xor;
+pub const INVERT: BooleanOperation = BooleanOperation::Invert;
+pub const LSHIFT: BooleanOperation = BooleanOperation::LShift;
+pub const RSHIFT: BooleanOperation = BooleanOperation::RShift;
+pub const ARSHIFT: BooleanOperation = BooleanOperation::ARShift;
 
 /// Constants for logical operations
 pub const LESS_THAN: LogicalOperation = LogicalOperation::LessThan;
 pub const GREATER_THAN: LogicalOperation = LogicalOperation::GreaterThan;
 pub const EQUAL: LogicalOperation = LogicalOperation::Equal;
+pub const NOT_EQUAL: LogicalOperation = LogicalOperation::NotEqual;
+pub const LESS_OR_EQUAL: LogicalOperation = LogicalOperation::LessOrEqual;
+pub const GREATER_OR_EQUAL: LogicalOperation = LogicalOperation::GreaterOrEqual;
+pub const ZERO_EQUAL: LogicalOperation = LogicalOperation::ZeroEqual;
+pub const ZERO_LESS: LogicalOperation = LogicalOperation::ZeroLess;
+pub const ZERO_GREATER: LogicalOperation = LogicalOperation::ZeroGreater;
+pub const ZERO_NOT_EQUAL: LogicalOperation = LogicalOperation::ZeroNotEqual;
+pub const U_LESS_THAN: LogicalOperation = LogicalOperation::ULessThan;
+pub const U_GREATER_THAN: LogicalOperation = LogicalOperation::UGreaterThan;
+pub const WITHIN: LogicalOperation = LogicalOperation::Within;
 
 /// Enumeration for boolean operations.
 /// This enum defines the types of operations that can be performed.
 /// The operations include:
-/// - And (&&)
-/// - Or (||)
-/// - Not (!)
-///     These operations are used to perform logical operations on boolean values.
-#[derive(Debug, PartialEq)]
+/// - And (&)
+/// - Or (|)
+/// - Not (0=)
+/// - Xor (^)
+/// - Invert (!)
+/// - LShift (<<)
+/// - RShift (>>, filling with zeros)
+/// - ARShift (>>, filling with the sign bit)
+///   These operations are used to perform bitwise operations on cells, or logical
+///   operations on flags with the `Logical` boolean mode.
+#[derive(Debug, PartialEq, Clone)]
 pub enum BooleanOperation {
     And,
     Or,
     Not,
+    Xor,
+    Invert,
+    LShift,
+    RShift,
+    ARShift,
+}
+
+/// Semantics of `AND`, `OR` and `XOR`.
+/// - Bitwise: They operate on every bit of the cells, as in standard Forth (`12 10 AND` is 8).
+/// - Logical: They only return `FORTH_TRUE` when their operands are exactly `FORTH_TRUE`,
+///   as in the original interpreter (`12 10 AND` is 0). Kept for compatibility.
+///
+/// `NOT` is always logical, while `INVERT` and the shifts are always bitwise.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum BooleanMode {
+    #[default]
+    Bitwise,
+    Logical,
 }
 
 /// Enumeration for logical operations.
@@ -37,23 +78,47 @@ pub enum BooleanOperation {
 /// - LessThan (<)
 /// - GreaterThan (>)
 /// - Equal (=)
-///     These operations are used to compare two values and return a boolean result.
-#[derive(Debug, PartialEq)]
+/// - NotEqual (<>)
+/// - LessOrEqual (<=)
+/// - GreaterOrEqual (>=)
+/// - ZeroEqual (0=), ZeroLess (0<), ZeroGreater (0>) and ZeroNotEqual (0<>), which compare
+///   a single value with zero
+/// - ULessThan (U<) and UGreaterThan (U>), which compare the values as unsigned numbers
+/// - Within (WITHIN), which checks if a value is in the range `[low, high)`
+///   These operations are used to compare values and return a boolean result.
+#[derive(Debug, PartialEq, Clone)]
 pub enum LogicalOperation {
     LessThan,
     GreaterThan,
     Equal,
+    NotEqual,
+    LessOrEqual,
+    GreaterOrEqual,
+    ZeroEqual,
+    ZeroLess,
+    ZeroGreater,
+    ZeroNotEqual,
+    ULessThan,
+    UGreaterThan,
+    Within,
 }
 
 /// A struct to manage boolean operations.
 /// It provides methods to execute boolean and logical operations.
 ///
 /// # Methods
+/// - `with_boolean_mode`: Sets the semantics of `AND`, `OR` and `XOR`.
+/// - `with_cell_size`: Sets the width of the cells, used by the shifts.
 /// - `execute_boolean_operation`: Executes a boolean operation on two operands.
-/// - `execute_logical_operations`: Executes a logical operation on two operands.
+/// - `execute_logical_operations`: Executes a logical operation on its operands.
+/// - `logical_arity`: Returns the number of operands of a logical operation.
 /// - `is_not`: Checks if the operation is a NOT operation.
+/// - `is_unary`: Checks if the operation takes a single operand.
 #[derive(Debug, PartialEq)]
-pub struct BooleanOperationManager {}
+pub struct BooleanOperationManager {
+    mode: BooleanMode,
+    cell_size: CellSize,
+}
 
 impl Default for BooleanOperationManager {
     fn default() -> Self {
@@ -64,34 +129,47 @@ impl Default for BooleanOperationManager {
 impl BooleanOperationManager {
     /// Creates a new instance of the BooleanOperationManager.
     pub fn new() -> Self {
-        BooleanOperationManager {}
+        BooleanOperationManager {
+            mode: BooleanMode::default(),
+            cell_size: CellSize::default(),
+        }
+    }
+
+    /// Sets the semantics of `AND`, `OR` and `XOR`: bitwise (the default) or logical.
+    pub fn with_boolean_mode(mut self, mode: BooleanMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the width of the cells, which limits the bits moved by the shifts.
+    pub fn with_cell_size(mut self, cell_size: CellSize) -> Self {
+        self.cell_size = cell_size;
+        self
     }
 
     /// Executes a boolean operation on two operands.
     /// The second operand is optional and defaults to 0 if not provided.
-    /// Returns the result of the operation as an `i16` value.
-    /// The result is `FORTH_TRUE` if the operation is successful, otherwise `FORTH_FALSE`.
+    /// For the shifts, the first operand is the value and the second one the number of bits.
+    /// Returns the result of the operation as a `Cell` value.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::boolean_operations::{AND, BooleanMode, BooleanOperationManager, LSHIFT};
+    /// let mut manager = BooleanOperationManager::new();
+    /// assert_eq!(manager.execute_boolean_operation(&AND, 12, Some(10)), 8);
+    /// assert_eq!(manager.execute_boolean_operation(&LSHIFT, 1, Some(4)), 16);
+    ///
+    /// let mut manager = BooleanOperationManager::new().with_boolean_mode(BooleanMode::Logical);
+    /// assert_eq!(manager.execute_boolean_operation(&AND, 12, Some(10)), 0);
+    /// ```
     pub fn execute_boolean_operation(
         &mut self,
         operation: &BooleanOperation,
-        op1: i16,
-        op2: Option<i16>,
-    ) -> i16 {
+        op1: Cell,
+        op2: Option<Cell>,
+    ) -> Cell {
+        let op2 = op2.unwrap_or(0);
         match operation {
-            BooleanOperation::And => {
-                if op1 == FORTH_TRUE && op2.unwrap_or(0) == FORTH_TRUE {
-                    FORTH_TRUE
-                } else {
-                    FORTH_FALSE
-                }
-            }
-            BooleanOperation::Or => {
-                if op1 == FORTH_TRUE || op2.unwrap_or(0) == FORTH_TRUE {
-                    FORTH_TRUE
-                } else {
-                    FORTH_FALSE
-                }
-            }
             BooleanOperation::Not => {
                 if op1 == 0 {
                     FORTH_TRUE
@@ -99,44 +177,127 @@ impl BooleanOperationManager {
                     FORTH_FALSE
                 }
             }
+            BooleanOperation::Invert => !op1,
+            BooleanOperation::LShift => self.shift_left(op1, op2),
+            BooleanOperation::RShift => self.shift_right(op1, op2),
+            BooleanOperation::ARShift => op1 >> self.shift_amount(op2).min(Cell::BITS - 1),
+            _ if self.mode == BooleanMode::Bitwise => match operation {
+                BooleanOperation::And => op1 & op2,
+                BooleanOperation::Or => op1 | op2,
+                _ => op1 ^ op2,
+            },
+            _ => self.execute_logical_boolean_operation(operation, op1, op2),
         }
     }
 
-    /// Executes a logical operation on two operands.
-    /// Returns the result of the operation as an `i16` value.
+    /// Executes `AND`, `OR` or `XOR` with the logical semantics of the original interpreter.
+    fn execute_logical_boolean_operation(
+        &self,
+        operation: &BooleanOperation,
+        op1: Cell,
+        op2: Cell,
+    ) -> Cell {
+        let result = match operation {
+            BooleanOperation::And => op1 == FORTH_TRUE && op2 == FORTH_TRUE,
+            BooleanOperation::Or => op1 == FORTH_TRUE || op2 == FORTH_TRUE,
+            _ => (op1 == FORTH_TRUE) != (op2 == FORTH_TRUE),
+        };
+        if result { FORTH_TRUE } else { FORTH_FALSE }
+    }
+
+    /// Number of bits to shift, reading the operand as unsigned.
+    /// Amounts that do not fit in a `u32` are clamped, since they shift every bit out anyway.
+    fn shift_amount(&self, bits: Cell) -> u32 {
+        u32::try_from(self.cell_size.unsigned(bits)).unwrap_or(u32::MAX)
+    }
+
+    /// Shifts the bits of a cell to the left, filling with zeros.
+    fn shift_left(&self, value: Cell, bits: Cell) -> Cell {
+        let bits = self.shift_amount(bits);
+        if bits >= self.cell_size.bits() {
+            return 0;
+        }
+        self.cell_size.wrap((value as i128) << bits)
+    }
+
+    /// Shifts the bits of a cell to the right, filling with zeros.
+    fn shift_right(&self, value: Cell, bits: Cell) -> Cell {
+        let bits = self.shift_amount(bits);
+        if bits >= self.cell_size.bits() {
+            return 0;
+        }
+        self.cell_size
+            .wrap((self.cell_size.unsigned(value) >> bits) as i128)
+    }
+
+    /// Executes a logical operation on its operands, given in the order they were pushed.
+    /// Returns the result of the operation as a `Cell` value.
     /// The result is `FORTH_TRUE` if the operation is successful, otherwise `FORTH_FALSE`.
+    /// The number of operands must be the one returned by `logical_arity`;
+    /// missing operands are taken as 0.
     /// The operations supported are:
-    /// - LessThan (<)
-    /// - GreaterThan (>)
-    /// - Equal (=)  
+    /// - LessThan (<), GreaterThan (>), Equal (=), NotEqual (<>), LessOrEqual (<=),
+    ///   GreaterOrEqual (>=), ULessThan (U<) and UGreaterThan (U>), with two operands
+    /// - ZeroEqual (0=), ZeroLess (0<), ZeroGreater (0>) and ZeroNotEqual (0<>), with one operand
+    /// - Within (WITHIN), with three operands: the value, the low and the high limit
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::boolean_operations::{BooleanOperationManager, FORTH_TRUE, U_LESS_THAN, WITHIN};
+    /// let mut manager = BooleanOperationManager::new();
+    /// assert_eq!(manager.execute_logical_operations(&U_LESS_THAN, &[1, -1]), FORTH_TRUE);
+    /// assert_eq!(manager.execute_logical_operations(&WITHIN, &[5, 1, 10]), FORTH_TRUE);
+    /// ```
     pub fn execute_logical_operations(
         &mut self,
         operation: &LogicalOperation,
-        op1: i16,
-        op2: i16,
-    ) -> i16 {
-        match operation {
-            LogicalOperation::LessThan => {
-                if op1 < op2 {
-                    FORTH_TRUE
-                } else {
-                    FORTH_FALSE
-                }
+        operands: &[Cell],
+    ) -> Cell {
+        let operand = |index: usize| operands.get(index).copied().unwrap_or(0);
+        let (op1, op2, op3) = (operand(0), operand(1), operand(2));
+
+        let result = match operation {
+            LogicalOperation::LessThan => op1 < op2,
+            LogicalOperation::GreaterThan => op1 > op2,
+            LogicalOperation::Equal => op1 == op2,
+            LogicalOperation::NotEqual => op1 != op2,
+            LogicalOperation::LessOrEqual => op1 <= op2,
+            LogicalOperation::GreaterOrEqual => op1 >= op2,
+            LogicalOperation::ZeroEqual => op1 == 0,
+            LogicalOperation::ZeroLess => op1 < 0,
+            LogicalOperation::ZeroGreater => op1 > 0,
+            LogicalOperation::ZeroNotEqual => op1 != 0,
+            LogicalOperation::ULessThan => {
+                self.cell_size.unsigned(op1) < self.cell_size.unsigned(op2)
             }
-            LogicalOperation::GreaterThan => {
-                if op1 > op2 {
-                    FORTH_TRUE
-                } else {
-                    FORTH_FALSE
-                }
-            }
-            LogicalOperation::Equal => {
-                if op1 == op2 {
-                    FORTH_TRUE
-                } else {
-                    FORTH_FALSE
-                }
+            LogicalOperation::UGreaterThan => {
+                self.cell_size.unsigned(op1) > self.cell_size.unsigned(op2)
             }
+            LogicalOperation::Within => self.within(op1, op2, op3),
+        };
+        if result { FORTH_TRUE } else { FORTH_FALSE }
+    }
+
+    /// Checks if `value` is in the range `[low, high)`, like the standard definition of `WITHIN`:
+    /// `value - low` is compared with `high - low` as unsigned numbers, so the range
+    /// wraps around when `high` is lower than `low`.
+    fn within(&self, value: Cell, low: Cell, high: Cell) -> bool {
+        let distance = |to: Cell| {
+            let difference = self.cell_size.wrap(to as i128 - low as i128);
+            self.cell_size.unsigned(difference)
+        };
+        distance(value) < distance(high)
+    }
+
+    /// Returns the number of operands taken from the stack by a logical operation.
+    pub fn logical_arity(&self, operation: &LogicalOperation) -> usize {
+        match operation {
+            LogicalOperation::ZeroEqual
+            | LogicalOperation::ZeroLess
+            | LogicalOperation::ZeroGreater
+            | LogicalOperation::ZeroNotEqual => 1,
+            LogicalOperation::Within => 3,
+            _ => 2,
         }
     }
 
@@ -144,6 +305,11 @@ impl BooleanOperationManager {
     pub fn is_not(&self, operation: &BooleanOperation) -> bool {
         matches!(operation, BooleanOperation::Not)
     }
+
+    /// Checks if the operation takes a single operand (`NOT` and `INVERT`).
+    pub fn is_unary(&self, operation: &BooleanOperation) -> bool {
+        matches!(operation, BooleanOperation::Not | BooleanOperation::Invert)
+    }
 }
 
 #[cfg(test)]
@@ -193,28 +359,217 @@ mod tests {
         let mut manager = BooleanOperationManager::new();
 
         assert_eq!(
-            manager.execute_logical_operations(&LogicalOperation::LessThan, 1, 2),
+            manager.execute_logical_operations(&LogicalOperation::LessThan, &[1, 2]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&LogicalOperation::LessThan, &[2, 1]),
+            FORTH_FALSE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&LogicalOperation::GreaterThan, &[2, 1]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&LogicalOperation::GreaterThan, &[1, 2]),
+            FORTH_FALSE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&LogicalOperation::Equal, &[1, 1]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&LogicalOperation::Equal, &[1, 2]),
+            FORTH_FALSE
+        );
+    }
+
+    #[test]
+    fn bitwise_mode_operates_on_every_bit() {
+        let mut manager = BooleanOperationManager::new();
+
+        assert_eq!(manager.execute_boolean_operation(&AND, 12, Some(10)), 8);
+        assert_eq!(manager.execute_boolean_operation(&OR, 12, Some(10)), 14);
+        assert_eq!(manager.execute_boolean_operation(&XOR, 12, Some(10)), 6);
+        assert_eq!(manager.execute_boolean_operation(&INVERT, 0, None), -1);
+        assert_eq!(manager.execute_boolean_operation(&INVERT, 5, None), -6);
+    }
+
+    #[test]
+    fn logical_mode_only_accepts_true_flags() {
+        let mut manager = BooleanOperationManager::new().with_boolean_mode(BooleanMode::Logical);
+
+        assert_eq!(manager.execute_boolean_operation(&AND, 12, Some(10)), 0);
+        assert_eq!(manager.execute_boolean_operation(&OR, 12, Some(-1)), -1);
+        assert_eq!(manager.execute_boolean_operation(&XOR, -1, Some(-1)), 0);
+        assert_eq!(manager.execute_boolean_operation(&XOR, -1, Some(0)), -1);
+    }
+
+    #[test]
+    fn shifts_move_the_bits_of_a_cell() {
+        let mut manager = BooleanOperationManager::new();
+
+        assert_eq!(manager.execute_boolean_operation(&LSHIFT, 1, Some(4)), 16);
+        assert_eq!(
+            manager.execute_boolean_operation(&LSHIFT, 1, Some(15)),
+            -32768
+        );
+        assert_eq!(manager.execute_boolean_operation(&LSHIFT, 1, Some(16)), 0);
+        assert_eq!(
+            manager.execute_boolean_operation(&RSHIFT, -1, Some(1)),
+            32767
+        );
+        assert_eq!(manager.execute_boolean_operation(&RSHIFT, 16, Some(2)), 4);
+        assert_eq!(
+            manager.execute_boolean_operation(&ARSHIFT, -16, Some(2)),
+            -4
+        );
+        assert_eq!(
+            manager.execute_boolean_operation(&ARSHIFT, -1, Some(40)),
+            -1
+        );
+    }
+
+    #[test]
+    fn shifts_depend_on_the_cell_size() {
+        let mut manager = BooleanOperationManager::new().with_cell_size(CellSize::I32);
+
+        assert_eq!(
+            manager.execute_boolean_operation(&LSHIFT, 1, Some(16)),
+            65536
+        );
+        assert_eq!(
+            manager.execute_boolean_operation(&RSHIFT, -1, Some(1)),
+            i32::MAX as Cell
+        );
+    }
+
+    #[test]
+    fn can_execute_the_remaining_comparisons() {
+        let mut manager = BooleanOperationManager::new();
+
+        assert_eq!(
+            manager.execute_logical_operations(&NOT_EQUAL, &[1, 2]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&NOT_EQUAL, &[2, 2]),
+            FORTH_FALSE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&LESS_OR_EQUAL, &[2, 2]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&LESS_OR_EQUAL, &[3, 2]),
+            FORTH_FALSE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&GREATER_OR_EQUAL, &[2, 2]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&GREATER_OR_EQUAL, &[1, 2]),
+            FORTH_FALSE
+        );
+    }
+
+    #[test]
+    fn can_compare_with_zero() {
+        let mut manager = BooleanOperationManager::new();
+
+        assert_eq!(
+            manager.execute_logical_operations(&ZERO_EQUAL, &[0]),
             FORTH_TRUE
         );
         assert_eq!(
-            manager.execute_logical_operations(&LogicalOperation::LessThan, 2, 1),
+            manager.execute_logical_operations(&ZERO_EQUAL, &[3]),
             FORTH_FALSE
         );
         assert_eq!(
-            manager.execute_logical_operations(&LogicalOperation::GreaterThan, 2, 1),
+            manager.execute_logical_operations(&ZERO_LESS, &[-3]),
             FORTH_TRUE
         );
         assert_eq!(
-            manager.execute_logical_operations(&LogicalOperation::GreaterThan, 1, 2),
+            manager.execute_logical_operations(&ZERO_LESS, &[0]),
             FORTH_FALSE
         );
         assert_eq!(
-            manager.execute_logical_operations(&LogicalOperation::Equal, 1, 1),
+            manager.execute_logical_operations(&ZERO_GREATER, &[3]),
             FORTH_TRUE
         );
         assert_eq!(
-            manager.execute_logical_operations(&LogicalOperation::Equal, 1, 2),
+            manager.execute_logical_operations(&ZERO_GREATER, &[0]),
             FORTH_FALSE
         );
+        assert_eq!(
+            manager.execute_logical_operations(&ZERO_NOT_EQUAL, &[3]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&ZERO_NOT_EQUAL, &[0]),
+            FORTH_FALSE
+        );
+    }
+
+    #[test]
+    fn can_compare_unsigned_values() {
+        let mut manager = BooleanOperationManager::new();
+
+        assert_eq!(
+            manager.execute_logical_operations(&U_LESS_THAN, &[1, -1]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&U_LESS_THAN, &[-1, 1]),
+            FORTH_FALSE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&U_GREATER_THAN, &[-1, 1]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&U_GREATER_THAN, &[1, 1]),
+            FORTH_FALSE
+        );
+    }
+
+    #[test]
+    fn within_checks_a_half_open_range() {
+        let mut manager = BooleanOperationManager::new();
+
+        assert_eq!(
+            manager.execute_logical_operations(&WITHIN, &[1, 1, 10]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&WITHIN, &[9, 1, 10]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&WITHIN, &[10, 1, 10]),
+            FORTH_FALSE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&WITHIN, &[-5, -10, 0]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&WITHIN, &[0, 10, 1]),
+            FORTH_TRUE
+        );
+        assert_eq!(
+            manager.execute_logical_operations(&WITHIN, &[5, 10, 1]),
+            FORTH_FALSE
+        );
+    }
+
+    #[test]
+    fn comparisons_with_zero_and_within_have_their_own_arity() {
+        let manager = BooleanOperationManager::new();
+
+        assert_eq!(manager.logical_arity(&ZERO_LESS), 1);
+        assert_eq!(manager.logical_arity(&U_LESS_THAN), 2);
+        assert_eq!(manager.logical_arity(&WITHIN), 3);
     }
 }
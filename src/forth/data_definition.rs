@@ -0,0 +1,22 @@
+/// Constants for the defining words that create data in Forth
+/// This includes the keywords VARIABLE, CONSTANT, VALUE, CREATE and TO
+pub const VARIABLE: DataDefinition = DataDefinition::Variable;
+pub const CONSTANT: DataDefinition = DataDefinition::Constant;
+pub const VALUE: DataDefinition = DataDefinition::Value;
+pub const CREATE: DataDefinition = DataDefinition::Create;
+pub const TO: DataDefinition = DataDefinition::To;
+
+/// Represents a word that takes the following name from the input to work with data.
+/// - Variable: Defines a word that pushes the address of a new cell.
+/// - Constant: Defines a word that pushes the value taken from the stack.
+/// - Value: Defines a word that pushes a value that can be changed with `TO`.
+/// - Create: Defines a word that pushes the address of the next free cell.
+/// - To: Changes the value of a word defined with `VALUE`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DataDefinition {
+    Variable,
+    Constant,
+    Value,
+    Create,
+    To,
+}
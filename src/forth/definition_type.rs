@@ -4,18 +4,78 @@ pub const IF: DefinitionType = DefinitionType::If;
 pub const ELSE: DefinitionType = DefinitionType::Else;
 pub const THEN: DefinitionType = DefinitionType::Then;
 
+/// Constants for counted loops in Forth
+/// This includes the keywords DO, LOOP, +LOOP, I, J, LEAVE, UNLOOP and EXIT
+pub const DO: DefinitionType = DefinitionType::Do;
+pub const LOOP: DefinitionType = DefinitionType::Loop;
+pub const PLUS_LOOP: DefinitionType = DefinitionType::PlusLoop;
+pub const LOOP_INDEX: DefinitionType = DefinitionType::LoopIndex;
+pub const OUTER_LOOP_INDEX: DefinitionType = DefinitionType::OuterLoopIndex;
+pub const LEAVE: DefinitionType = DefinitionType::Leave;
+pub const UNLOOP: DefinitionType = DefinitionType::Unloop;
+pub const EXIT: DefinitionType = DefinitionType::Exit;
+
+/// Constants for indefinite loops in Forth
+/// This includes the keywords BEGIN, UNTIL, WHILE, REPEAT and AGAIN
+pub const BEGIN: DefinitionType = DefinitionType::Begin;
+pub const UNTIL: DefinitionType = DefinitionType::Until;
+pub const WHILE: DefinitionType = DefinitionType::While;
+pub const REPEAT: DefinitionType = DefinitionType::Repeat;
+pub const AGAIN: DefinitionType = DefinitionType::Again;
+
+/// Constant for the word that calls the definition being compiled
+pub const RECURSE: DefinitionType = DefinitionType::Recurse;
+
 /// Represents the type of a definition in Forth.
-/// This includes user-defined names, conditional definitions (if, else, then)
-#[derive(Debug, PartialEq)]
+/// This includes user-defined names, conditional definitions (if, else, then),
+/// counted loops (do, loop, +loop) with their index and exit words,
+/// indefinite loops (begin, until, while, repeat, again) and recursion.
+#[derive(Debug, PartialEq, Clone)]
 pub enum DefinitionType {
     Name(String),
     If,
     Else,
     Then,
+    Do,
+    Loop,
+    PlusLoop,
+    LoopIndex,
+    OuterLoopIndex,
+    Leave,
+    Unloop,
+    Exit,
+    Begin,
+    Until,
+    While,
+    Repeat,
+    Again,
+    Recurse,
 }
 
 impl DefinitionType {
     pub fn name(name: impl Into<String>) -> Self {
         DefinitionType::Name(name.into())
     }
+
+    /// Checks if the definition type opens a control structure (IF, DO, BEGIN).
+    pub fn opens_block(&self) -> bool {
+        matches!(
+            self,
+            DefinitionType::If | DefinitionType::Do | DefinitionType::Begin
+        )
+    }
+
+    /// Checks if the definition type closes a control structure
+    /// (THEN, LOOP, +LOOP, UNTIL, REPEAT, AGAIN).
+    pub fn closes_block(&self) -> bool {
+        matches!(
+            self,
+            DefinitionType::Then
+                | DefinitionType::Loop
+                | DefinitionType::PlusLoop
+                | DefinitionType::Until
+                | DefinitionType::Repeat
+                | DefinitionType::Again
+        )
+    }
 }
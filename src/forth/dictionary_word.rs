@@ -0,0 +1,27 @@
+/// Constants for the words that work with the dictionary in Forth
+/// This includes the keywords WORDS, SEE, FORGET and MARKER
+pub const WORDS: DictionaryWord = DictionaryWord::Words;
+pub const SEE: DictionaryWord = DictionaryWord::See;
+pub const FORGET: DictionaryWord = DictionaryWord::Forget;
+pub const MARKER: DictionaryWord = DictionaryWord::Marker;
+
+/// Represents a word that works with the dictionary of user-defined words.
+/// - Words: Prints the names of the words that can be used, from the newest to the oldest.
+/// - See: Prints the source of the word whose name follows it.
+/// - Forget: Removes the word whose name follows it and every word defined after it,
+///   releasing the data space they took.
+/// - Marker: Defines a word that, when executed, forgets itself and every word defined after it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DictionaryWord {
+    Words,
+    See,
+    Forget,
+    Marker,
+}
+
+impl DictionaryWord {
+    /// Checks if the word takes the following name from the input.
+    pub fn takes_name(&self) -> bool {
+        !matches!(self, DictionaryWord::Words)
+    }
+}
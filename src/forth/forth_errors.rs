@@ -1,17 +1,52 @@
 use std::fmt;
 
+/// Token that could not be resolved while parsing, with the column where it starts.
+/// When the error is also located, the column is not repeated in its message.
+#[derive(Debug, PartialEq)]
+pub struct UnknownToken {
+    pub token: String,
+    pub position: usize,
+}
+
+impl UnknownToken {
+    pub fn new(token: impl Into<String>, position: usize) -> Self {
+        UnknownToken {
+            token: token.into(),
+            position,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum ForthError {
     InvalidWord,
-    // UnknownWord(String),
-    UnknownWord,
+    UnknownWord(Option<UnknownToken>),
+    UnbalancedControlStructure,
+    CompileOnlyWord,
+    InterpretOnlyWord,
+    UnterminatedString(usize),
+    MissingName,
+    InvalidAddress,
+    DataSpaceOverflow,
+    MaxCallDepthExceeded,
 }
 
 impl fmt::Display for ForthError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             ForthError::InvalidWord => write!(f, "invalid-word"),
-            ForthError::UnknownWord => write!(f, "?"),
+            ForthError::UnknownWord(None) => write!(f, "?"),
+            ForthError::UnknownWord(Some(ref unknown)) => {
+                write!(f, "{} ? at column {}", unknown.token, unknown.position)
+            }
+            ForthError::UnbalancedControlStructure => write!(f, "unbalanced-control-structure"),
+            ForthError::CompileOnlyWord => write!(f, "compile-only-word"),
+            ForthError::InterpretOnlyWord => write!(f, "interpret-only-word"),
+            ForthError::UnterminatedString(_) => write!(f, "unterminated-string"),
+            ForthError::MissingName => write!(f, "missing-name"),
+            ForthError::InvalidAddress => write!(f, "invalid-memory-address"),
+            ForthError::DataSpaceOverflow => write!(f, "data-space-overflow"),
+            ForthError::MaxCallDepthExceeded => write!(f, "max-call-depth-exceeded"),
         }
     }
 }
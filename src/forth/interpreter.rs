@@ -1,10 +1,18 @@
+use super::boolean_operations::BooleanMode;
+use super::data_definition::DataDefinition;
 use super::definition_type::DefinitionType;
+use super::dictionary_word::DictionaryWord;
 use super::forth_errors::ForthError;
 use super::intruction::Instruction;
+use super::output_instructions::{OutputInstruction, OutputMode};
 use super::parser::Parser;
-use super::word::{WordDefinitionManager, WordType};
+use super::span::Span;
+use super::word::{CaseSensitivity, WordDefinitionManager, WordType};
 use super::word_data::WordData;
-use crate::errors::Error;
+use crate::calculator::arithmetic_policy::ArithmeticPolicy;
+use crate::calculator::division_mode::DivisionMode;
+use crate::cell::{Cell, CellSize, is_literal};
+use crate::errors::{Error, Location, Traceback};
 use crate::handler::instructions_handler::ExecutionHandler;
 use std::io::Write;
 
@@ -27,10 +35,18 @@ use std::io::Write;
 /// - `boolean_manager`: The manager for handling boolean operations.
 /// - `writer`: An optional writer for outputting results.
 /// - `parser`: The parser used for interpreting Forth instructions.
+/// - `source_name`: An optional name of the source code, used to locate errors.
+/// - `traceback`: The chain of user-defined words that were being executed when the last
+///   error was raised, if any, with the line where the outermost was called.
+/// - `data_space_marks`: The number of definitions before each word defined by the interpreter,
+///   with the next free cell of the data space at that moment, used by `FORGET` and markers.
 pub struct Forth<W: Write> {
     handler: ExecutionHandler<W>,
     word_manager: WordDefinitionManager,
     parser: Parser,
+    source_name: Option<String>,
+    traceback: Option<Traceback>,
+    data_space_marks: Vec<(usize, Cell)>,
 }
 
 impl<W: Write> Forth<W> {
@@ -52,18 +68,169 @@ impl<W: Write> Forth<W> {
             word_manager: WordDefinitionManager::new(),
             handler: ExecutionHandler::new(stack_capacity, writer),
             parser: Parser::new(),
+            source_name: None,
+            traceback: None,
+            data_space_marks: Vec::new(),
         }
     }
 
+    /// Sets the name of the source code being interpreted (e.g. the path of the file),
+    /// which is reported together with the line and column of located errors.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use std::io::Sink;
+    /// let forth: Forth<Sink> = Forth::new(None, None).with_source_name("input.fth");
+    /// ```
+    pub fn with_source_name(mut self, source_name: impl Into<String>) -> Self {
+        self.source_name = Some(source_name.into());
+        self
+    }
+
+    /// Sets the size of the data space, in bytes.
+    /// If not provided, the default size is used.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use std::io::Sink;
+    /// let forth: Forth<Sink> = Forth::new(None, None).with_data_space_size(Some(1024));
+    /// ```
+    pub fn with_data_space_size(mut self, size: Option<usize>) -> Self {
+        self.handler = self.handler.with_data_space_size(size);
+        self
+    }
+
+    /// Sets the maximum number of nested calls to user-defined words.
+    /// If not provided, the default maximum is used.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use std::io::Sink;
+    /// let forth: Forth<Sink> = Forth::new(None, None).with_max_call_depth(Some(64));
+    /// ```
+    pub fn with_max_call_depth(mut self, max_call_depth: Option<usize>) -> Self {
+        self.word_manager = self.word_manager.with_max_call_depth(max_call_depth);
+        self
+    }
+
+    /// Sets how the names of user-defined words are compared: case insensitive (the default)
+    /// or case sensitive. Names keep the spelling of their definition in `WORDS` and `SEE`.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use rust_forth::forth::word::{CaseSensitivity, WordType};
+    ///# use std::io::Sink;
+    /// let mut forth: Forth<Sink> =
+    ///     Forth::new(None, None).with_case_sensitivity(CaseSensitivity::Sensitive);
+    /// let instructions = forth.parse_instructions(": Square dup * ;".to_string());
+    /// let _ = forth.process_instructions(instructions);
+    ///
+    /// assert!(forth.is_word_defined(&WordType::UserDefined("Square".to_string())));
+    /// assert!(!forth.is_word_defined(&WordType::UserDefined("square".to_string())));
+    /// ```
+    pub fn with_case_sensitivity(mut self, case_sensitivity: CaseSensitivity) -> Self {
+        self.word_manager = self.word_manager.with_case_sensitivity(case_sensitivity);
+        self
+    }
+
+    /// Enables or disables the strict compile mode of the parser.
+    /// In strict mode, undefined words inside definitions and number literals that do not fit
+    /// in a cell are reported by `try_parse_instructions`.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use std::io::Sink;
+    /// let forth: Forth<Sink> = Forth::new(None, None).with_strict_mode(true);
+    /// ```
+    pub fn with_strict_mode(mut self, strict: bool) -> Self {
+        self.parser = self.parser.with_strict_mode(strict);
+        self
+    }
+
+    /// Sets the width of the cells of the interpreter.
+    /// The sizes in bytes of the stack and the data space are kept, so wider cells
+    /// fit fewer times in them. Number literals and arithmetic results are limited to the width.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use rust_forth::cell::CellSize;
+    ///# use std::io::Sink;
+    /// let forth: Forth<Sink> = Forth::new(None, None).with_cell_size(CellSize::I32);
+    /// ```
+    pub fn with_cell_size(mut self, cell_size: CellSize) -> Self {
+        self.handler = self.handler.with_cell_size(cell_size);
+        self.parser = self.parser.with_cell_size(cell_size);
+        self
+    }
+
+    /// Sets the behaviour of the arithmetic operations when their result does not fit in a cell:
+    /// wrap around (the default), fail with `CalculatorError::Overflow` or saturate.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use rust_forth::calculator::arithmetic_policy::ArithmeticPolicy;
+    ///# use std::io::Sink;
+    /// let forth: Forth<Sink> = Forth::new(None, None).with_arithmetic_policy(ArithmeticPolicy::Checked);
+    /// ```
+    pub fn with_arithmetic_policy(mut self, policy: ArithmeticPolicy) -> Self {
+        self.handler = self.handler.with_arithmetic_policy(policy);
+        self
+    }
+
+    /// Sets the rounding of the quotients of `/`, `MOD`, `/MOD`, `*/` and `*/MOD`:
+    /// symmetric (truncated towards zero, the default) or floored.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use rust_forth::calculator::division_mode::DivisionMode;
+    ///# use std::io::Sink;
+    /// let forth: Forth<Sink> = Forth::new(None, None).with_division_mode(DivisionMode::Floored);
+    /// ```
+    pub fn with_division_mode(mut self, division: DivisionMode) -> Self {
+        self.handler = self.handler.with_division_mode(division);
+        self
+    }
+
+    /// Sets the semantics of `AND`, `OR` and `XOR`: bitwise as in standard Forth (the default),
+    /// or logical as in the original interpreter, where they only accept `-1` as true.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use rust_forth::forth::boolean_operations::BooleanMode;
+    ///# use std::io::Sink;
+    /// let forth: Forth<Sink> = Forth::new(None, None).with_boolean_mode(BooleanMode::Logical);
+    /// ```
+    pub fn with_boolean_mode(mut self, mode: BooleanMode) -> Self {
+        self.handler = self.handler.with_boolean_mode(mode);
+        self
+    }
+
+    /// Sets the spacing of the output of `EMIT` and `."`: followed by a space as in the
+    /// original interpreter (the default), or exactly their characters as in standard Forth.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use rust_forth::forth::output_instructions::OutputMode;
+    /// let mut forth = Forth::new(None, Some(Vec::new())).with_output_mode(OutputMode::Standard);
+    /// let instructions = forth.parse_instructions("72 emit 105 emit".to_string());
+    /// let _ = forth.process_instructions(instructions);
+    ///
+    /// assert_eq!(forth.get_writer().unwrap().as_slice(), b"Hi");
+    /// ```
+    pub fn with_output_mode(mut self, output_mode: OutputMode) -> Self {
+        self.handler = self.handler.with_output_mode(output_mode);
+        self
+    }
+
     /// Pushes an element onto the stack.
-    pub fn push(&mut self, element: i16) -> Result<(), Error> {
+    pub fn push(&mut self, element: Cell) -> Result<(), Error> {
         self.handler.handle_push_element(element)
     }
 
     /// Gets the current top element of the stack.
     /// This function returns a reference to the top element of the stack.
     /// If the stack is empty, it returns an error.
-    pub fn peek_stack(&mut self) -> Result<&i16, Error> {
+    pub fn peek_stack(&mut self) -> Result<&Cell, Error> {
         self.handler.handle_get_top_element()
     }
 
@@ -74,33 +241,177 @@ impl<W: Write> Forth<W> {
     /// # Arguments
     /// - `data`: A vector of Forth instructions to be processed.
     pub fn process_instructions(&mut self, data: Vec<Instruction>) -> Result<(), Error> {
-        for (i, element) in data.iter().enumerate() {
-            match element {
+        self.process_instructions_with_spans(data, Vec::new())
+    }
+
+    /// Processes a vector of Forth instructions together with the span of the token
+    /// that produced each of them, as returned by `parse_with_spans`.
+    /// Errors are located at the instruction that raised them, or at the instruction
+    /// inside a user-defined word when they are raised while the word is executed.
+    /// Control structures (IF, DO, BEGIN) are compiled on the fly and executed as a whole,
+    /// so they must be closed on the same line. The rest of the control words can only be
+    /// used inside them or inside a definition, and return `ForthError::CompileOnlyWord`.
+    /// # Arguments
+    /// - `data`: A vector of Forth instructions to be processed.
+    /// - `spans`: The span of each instruction.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use std::io::Sink;
+    /// let mut forth: Forth<Sink> = Forth::new(None, None).with_source_name("input.fth");
+    /// let (instructions, spans) = forth.parse_with_spans("1 +".to_string(), 7).unwrap();
+    ///
+    /// let result = forth.process_instructions_with_spans(instructions, spans);
+    ///
+    /// let error = result.unwrap_err();
+    /// assert_eq!(error.to_string(), "input.fth:7:3: stack-underflow in '+'");
+    /// ```
+    pub fn process_instructions_with_spans(
+        &mut self,
+        data: Vec<Instruction>,
+        spans: Vec<Span>,
+    ) -> Result<(), Error> {
+        self.traceback = None;
+        let mut i = 0;
+        while let Some(element) = data.get(i) {
+            let result = match element {
                 Instruction::StartDefinition => {
-                    self.define_word(data.into_iter().skip(i).collect())?;
-                    break;
+                    let definition_spans = spans.iter().skip(i).cloned().collect();
+                    let result =
+                        self.define_word(data.into_iter().skip(i).collect(), definition_spans);
+                    return result.map_err(|error| self.locate_error(error, &spans, i + 1));
                 }
+                Instruction::Literal(token) => match self.read_literal(token) {
+                    Ok(Some(number)) => self.handler.handle_push_element(number),
+                    Ok(None) => Ok(()),
+                    Err(error) => Err(error),
+                },
                 Instruction::DefinitionType(DefinitionType::Name(name)) => {
-                    self.execute_new_word(name)?;
+                    self.execute_new_word(name)
                 }
-                _ => self.handler.handle_instruction(element)?,
-            }
+                Instruction::DefinitionType(definition_type) if definition_type.opens_block() => {
+                    let end = find_end_of_structure(&data, i);
+                    let result = self.execute_control_structure(&data, &spans, i, end);
+                    result.map_err(|error| self.locate_error(error, &spans, i))?;
+                    i = end + 1;
+                    continue;
+                }
+                Instruction::DefinitionType(_) => Err(ForthError::CompileOnlyWord.into()),
+                Instruction::DataDefinition(definition, name) => self
+                    .define_tracked(name, |forth| {
+                        forth.execute_data_definition(*definition, name)
+                    }),
+                Instruction::DictionaryWord(word, name) => {
+                    self.execute_dictionary_word(*word, name)
+                }
+                _ => self.handler.handle_instruction(element),
+            };
+            result.map_err(|error| self.locate_error(error, &spans, i))?;
+            i += 1;
         }
         Ok(())
     }
 
+    /// Executes the control structure made by the instructions in `start..=end`,
+    /// compiling them into an anonymous definition.
+    fn execute_control_structure(
+        &mut self,
+        data: &[Instruction],
+        spans: &[Span],
+        start: usize,
+        end: usize,
+    ) -> Result<(), Error> {
+        let mut body = data.get(start..=end).unwrap_or_default().to_vec();
+        body.push(Instruction::end_definition());
+        let body_spans = spans
+            .iter()
+            .skip(start)
+            .take(end + 1 - start)
+            .cloned()
+            .collect();
+        let (body, body_spans) = self.compile_literals(body, body_spans, None)?;
+        self.word_manager
+            .run_anonymous(&mut self.handler, body, body_spans)
+    }
+
+    /// Attaches to an error the location of the instruction at `index`, if it has a span,
+    /// and the name of the source code.
+    /// The chain of words that were being executed is kept for `attach_traceback`,
+    /// starting at the line of the instruction.
+    fn locate_error(&mut self, error: Error, spans: &[Span], index: usize) -> Error {
+        let span = spans.get(index);
+        let words = self.word_manager.take_traceback();
+        if !words.is_empty() {
+            let line = span.map(|span| span.line);
+            self.traceback = Some(Traceback { words, line });
+        }
+
+        let error = match span {
+            Some(span) => error.locate(Location::new(span, span.word.to_string())),
+            None => error,
+        };
+        error.with_source(self.source_name.as_deref())
+    }
+
+    /// Attaches to an error returned by the last processed instructions the traceback
+    /// of the user-defined words that were being executed when it was raised, if any,
+    /// so that it can be reported with the rich error format.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use std::io::Sink;
+    /// let mut forth: Forth<Sink> = Forth::new(None, None);
+    /// let (instructions, spans) = forth.parse_with_spans(": square * ;".to_string(), 1).unwrap();
+    /// let _ = forth.process_instructions_with_spans(instructions, spans);
+    /// let (instructions, spans) = forth.parse_with_spans("3 square".to_string(), 2).unwrap();
+    ///
+    /// let error = forth.process_instructions_with_spans(instructions, spans).unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), "1:10: stack-underflow in 'square'");
+    /// let error = forth.attach_traceback(error);
+    /// assert_eq!(error.to_string(), "1:10: stack-underflow in 'square'\n  in square\n  at line 2");
+    /// ```
+    pub fn attach_traceback(&mut self, error: Error) -> Error {
+        let Some(traceback) = self.traceback.take() else {
+            return error;
+        };
+        let error = error.with_traceback(traceback.words);
+        match traceback.line {
+            Some(line) => error.called_at(line),
+            None => error,
+        }
+    }
+
     /// Processes a word definition in the Forth interpreter.
     /// This function looks for a word definition in the provided vector of Forth instructions.
     /// If a word definition is found, it extracts the word name and its body,
     /// and defines the new word in the word manager.
+    /// The names in its body that are not defined words are left out, with a warning
+    /// written to the standard error, apart from the output of the program.
     /// # Arguments
     /// - `data`: A vector of Forth instructions containing the word definition.
-    fn define_word(&mut self, data: Vec<Instruction>) -> Result<(), Error> {
+    /// - `spans`: The span of each instruction, which may be empty.
+    fn define_word(&mut self, data: Vec<Instruction>, spans: Vec<Span>) -> Result<(), Error> {
         for (i, element) in data.iter().enumerate() {
             if let Instruction::StartDefinition = element {
-                if let Instruction::DefinitionType(DefinitionType::Name(word_name)) = &data[i + 1] {
-                    let word_name = WordType::UserDefined(word_name.to_string());
-                    self.define_new_word(word_name, data.into_iter().skip(i + 2).collect())?;
+                if let Some(Instruction::DefinitionType(DefinitionType::Name(word_name))) =
+                    data.get(i + 1)
+                {
+                    let name = word_name.to_string();
+                    let body_spans = spans.into_iter().skip(i + 2).collect();
+                    let body = data.into_iter().skip(i + 2).collect();
+                    self.define_tracked(&name, |forth| {
+                        let (body, body_spans) =
+                            forth.compile_literals(body, body_spans, Some(&name))?;
+                        let word_name = WordType::UserDefined(name.to_string());
+                        forth
+                            .word_manager
+                            .define_new_word_with_spans(word_name, body, body_spans)?;
+                        for undefined in forth.word_manager.take_undefined_names() {
+                            eprintln!("undefined {}", undefined);
+                        }
+                        Ok(())
+                    })?;
                     break;
                 } else {
                     return Err(ForthError::InvalidWord.into());
@@ -110,6 +421,89 @@ impl<W: Write> Forth<W> {
         Ok(())
     }
 
+    /// Executes the words of a definition body that act while it is compiled, up to its end.
+    /// Number literals are read in the current base, and so are the names that are not
+    /// defined words, other than the name of the word being defined (`definition`).
+    /// The strings of `S"` and `C"` are stored in the data space once, being replaced
+    /// by the numbers that refer to them, and the text of `.(` is printed.
+    /// The strings are reserved for the definitions of words, and left in the transient
+    /// region for the control structures that are interpreted, which have no `definition`.
+    /// The spans, if any, are kept in line with the resulting instructions.
+    fn compile_literals(
+        &mut self,
+        body: Vec<Instruction>,
+        spans: Vec<Span>,
+        definition: Option<&str>,
+    ) -> Result<(Vec<Instruction>, Vec<Span>), Error> {
+        let mut instructions = Vec::new();
+        let mut instruction_spans = Vec::new();
+        let mut spans = spans.into_iter();
+
+        for element in body {
+            let span = spans.next();
+            let is_end = element == Instruction::EndDefinition;
+            let compiled = match element {
+                Instruction::Literal(token) => self
+                    .read_literal(&token)?
+                    .into_iter()
+                    .map(Instruction::number)
+                    .collect(),
+                Instruction::DefinitionType(DefinitionType::Name(name)) => {
+                    match self.name_as_number(&name, definition) {
+                        Some(number) => vec![Instruction::number(number)],
+                        None => vec![Instruction::definition_type(DefinitionType::Name(name))],
+                    }
+                }
+                Instruction::StringLiteral(kind, text) => {
+                    let values = match definition {
+                        Some(_) => self.handler.handle_store_string(kind, &text)?,
+                        None => self.handler.handle_transient_string(kind, &text)?,
+                    };
+                    values.into_iter().map(Instruction::number).collect()
+                }
+                Instruction::Output(OutputInstruction::DotParen(_)) => {
+                    self.handler.handle_instruction(&element)?;
+                    Vec::new()
+                }
+                element => vec![element],
+            };
+            if let Some(span) = span {
+                instruction_spans.resize(instruction_spans.len() + compiled.len(), span);
+            }
+            instructions.extend(compiled);
+            if is_end {
+                break;
+            }
+        }
+        Ok((instructions, instruction_spans))
+    }
+
+    /// Reads a number literal in the base that is current when it is interpreted or compiled,
+    /// which the parser can not know: `16 BASE ! FF` pushes 255.
+    /// Like the parser, it discards the literals that do not fit in a cell (`None`),
+    /// unless the strict compile mode is enabled.
+    /// Tokens that are not numbers in that base return `ForthError::UnknownWord`.
+    fn read_literal(&self, token: &str) -> Result<Option<Cell>, Error> {
+        let base = self.handler.handle_base();
+        match self.handler.handle_cell_size().parse_in_base(token, base) {
+            Some(number) => Ok(Some(number)),
+            None if is_literal(token, base) && !self.parser.is_strict() => Ok(None),
+            None => Err(ForthError::UnknownWord(None).into()),
+        }
+    }
+
+    /// Reads a name inside a body as a number in the current base, unless it is a defined
+    /// word or the word being defined (`definition`), as words take precedence.
+    fn name_as_number(&self, name: &str, definition: Option<&str>) -> Option<Cell> {
+        let is_definition = definition.is_some_and(|definition| {
+            self.word_manager.fold_name(definition) == self.word_manager.fold_name(name)
+        });
+        if is_definition || self.is_word_defined(&WordType::UserDefined(name.to_string())) {
+            return None;
+        }
+        self.read_literal(name).ok().flatten()
+    }
+
     /// Defines a new word in the Forth interpreter.
     /// This function takes a word name and its body (a vector of Forth instructions),
     /// and defines the new word in the word manager.
@@ -125,13 +519,141 @@ impl<W: Write> Forth<W> {
         Ok(())
     }
 
+    /// Executes a defining word that works with the data space.
+    /// `VARIABLE` and `VALUE` reserve a cell for the new word, `CONSTANT` and `VALUE`
+    /// take their initial content from the stack, `CREATE` names the next free cell
+    /// and `TO` changes the content of a value.
+    /// # Arguments
+    /// - `definition`: The defining word to be executed.
+    /// - `name`: The name of the word that is defined (or changed, for `TO`).
+    fn execute_data_definition(
+        &mut self,
+        definition: DataDefinition,
+        name: &str,
+    ) -> Result<(), Error> {
+        let word_name = WordType::UserDefined(name.to_string());
+        match definition {
+            DataDefinition::Variable => {
+                let address = self.handler.handle_allot(1)?;
+                self.define_constant(word_name, address)
+            }
+            DataDefinition::Constant => {
+                let value = self.handler.handle_drop_element()?;
+                self.define_constant(word_name, value)
+            }
+            DataDefinition::Value => {
+                let value = self.handler.handle_drop_element()?;
+                let address = self.handler.handle_allot(1)?;
+                self.handler.handle_store(address, value)?;
+                self.word_manager.define_value(word_name, address)
+            }
+            DataDefinition::Create => {
+                let address = self.handler.handle_here()?;
+                self.define_constant(word_name, address)
+            }
+            DataDefinition::To => {
+                let address = self
+                    .word_manager
+                    .value_address(&word_name)
+                    .ok_or(ForthError::UnknownWord(None))?;
+                let value = self.handler.handle_drop_element()?;
+                self.handler.handle_store(address, value)
+            }
+        }
+    }
+
+    /// Executes a word that works with the dictionary.
+    /// `WORDS` prints the names of the user-defined words and `SEE` prints the source
+    /// of the word that follows it, both spelled as they were defined and written through
+    /// the configured writer. `FORGET` removes the word that follows it and the ones defined
+    /// after it, and `MARKER` defines a word that does the same with itself when executed.
+    /// # Arguments
+    /// - `word`: The dictionary word to be executed.
+    /// - `name`: The name of the word it works with, if it takes one.
+    fn execute_dictionary_word(&mut self, word: DictionaryWord, name: &str) -> Result<(), Error> {
+        let word_name = WordType::UserDefined(name.to_string());
+        match word {
+            DictionaryWord::Words => {
+                let names = self.word_manager.word_names().join(" ");
+                self.handler.handle_write(&format!("{}\n", names));
+            }
+            DictionaryWord::See => {
+                let source = self
+                    .word_manager
+                    .source(&word_name)
+                    .ok_or(ForthError::UnknownWord(None))?;
+                self.handler.handle_write(&format!("{}\n", source));
+            }
+            DictionaryWord::Forget => self.forget(&word_name)?,
+            DictionaryWord::Marker => {
+                self.define_tracked(name, |forth| forth.word_manager.define_marker(word_name))?
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs an action that may define the word `name`.
+    /// If it does, the next free cell of the data space from before it is kept,
+    /// so that forgetting the word releases the cells taken since then,
+    /// and a warning is written if the word was already defined, as gforth does.
+    fn define_tracked(
+        &mut self,
+        name: &str,
+        define: impl FnOnce(&mut Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let definition_count = self.word_manager.definition_count();
+        let here = self.handler.handle_here()?;
+        let redefined = self.is_word_defined(&WordType::UserDefined(name.to_string()));
+
+        define(self)?;
+        if self.word_manager.definition_count() > definition_count {
+            if redefined {
+                self.handler.handle_write(&format!("redefined {}\n", name));
+            }
+            self.data_space_marks.push((definition_count, here));
+        }
+        Ok(())
+    }
+
+    /// Forgets a word and every word defined after it, restoring the dictionary
+    /// and the data space to their state before it was defined.
+    /// The data space is only restored for the words defined by the interpreter.
+    fn forget(&mut self, word_name: &WordType) -> Result<(), Error> {
+        let kept_definitions = self.word_manager.forget(word_name)?;
+        let forgotten_marks = self
+            .data_space_marks
+            .iter()
+            .position(|&(definition_count, _)| definition_count >= kept_definitions);
+        if let Some(position) = forgotten_marks {
+            let (_, here) = self.data_space_marks[position];
+            self.data_space_marks.truncate(position);
+            self.handler.handle_restore_here(here)?;
+        }
+        Ok(())
+    }
+
+    /// Defines a word that pushes a fixed number onto the stack.
+    fn define_constant(&mut self, word_name: WordType, value: Cell) -> Result<(), Error> {
+        let body = vec![Instruction::number(value), Instruction::end_definition()];
+        self.define_new_word(word_name, body)
+    }
+
     /// Executes a new word defined in the Forth interpreter.
     /// This function takes a word name and executes it if it is defined in the word manager.
+    /// Otherwise it is read as a number in the current base, which the parser can not know.
+    /// Words created with `MARKER` forget themselves and the words defined after them.
     /// # Arguments
     /// - `word_name`: The name of the word to be executed.
     fn execute_new_word(&mut self, word_name: &str) -> Result<(), Error> {
-        if !self.is_word_defined(&WordType::UserDefined(word_name.to_string())) {
-            return Err(ForthError::UnknownWord.into());
+        let word = WordType::UserDefined(word_name.to_string());
+        if !self.is_word_defined(&word) {
+            return match self.read_literal(word_name)? {
+                Some(number) => self.handler.handle_push_element(number),
+                None => Ok(()),
+            };
+        }
+        if self.word_manager.is_marker(&word) {
+            return self.forget(&word);
         }
 
         self.word_manager.run_word(&mut self.handler, word_name)?;
@@ -186,6 +708,23 @@ impl<W: Write> Forth<W> {
         self.word_manager.get_word_definition(word_name)
     }
 
+    /// Gets the stack effect of a word, written in the comment that follows its name.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use rust_forth::forth::word::WordType;
+    ///# use std::io::Sink;
+    /// let mut forth: Forth<Sink> = Forth::new(None, None);
+    /// let instructions = forth.parse_instructions(": double ( n -- 2n ) dup + ;".to_string());
+    /// let _ = forth.process_instructions(instructions);
+    ///
+    /// let stack_effect = forth.stack_effect(&WordType::UserDefined("double".to_string()));
+    /// assert_eq!(stack_effect, Some("n -- 2n"));
+    /// ```
+    pub fn stack_effect(&self, word_name: &WordType) -> Option<&str> {
+        self.word_manager.stack_effect(word_name)
+    }
+
     /// Gets the current content of the stack.
     /// This function returns a reference to the vector of elements currently in the stack.
     /// # Examples
@@ -201,7 +740,7 @@ impl<W: Write> Forth<W> {
     /// ```
     /// # Returns
     /// A reference to the vector of elements currently in the stack.
-    pub fn get_stack_content(&self) -> &Vec<i16> {
+    pub fn get_stack_content(&self) -> &Vec<Cell> {
         self.handler.handle_get_stack_content()
     }
 
@@ -231,7 +770,44 @@ impl<W: Write> Forth<W> {
     /// assert_eq!(instructions, expected_instructions);
     /// ```
     pub fn parse_instructions(&self, line: String) -> Vec<Instruction> {
-        self.parser.parse_instructions(line, &self.word_manager)
+        self.current_parser()
+            .parse_instructions(line, &self.word_manager)
+    }
+
+    /// Parses a line of Forth code into a vector of instructions, reporting the
+    /// errors found by the strict compile mode.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::interpreter::Forth;
+    ///# use rust_forth::forth::forth_errors::{ForthError, UnknownToken};
+    ///# use std::io::Sink;
+    /// let forth: Forth<Sink> = Forth::new(None, None).with_strict_mode(true);
+    ///
+    /// let result = forth.try_parse_instructions("1 40000".to_string());
+    ///
+    /// let expected_error = ForthError::UnknownWord(Some(UnknownToken::new("40000", 3)));
+    /// assert_eq!(result, Err(expected_error.into()));
+    /// ```
+    pub fn try_parse_instructions(&self, line: String) -> Result<Vec<Instruction>, Error> {
+        self.current_parser()
+            .try_parse_instructions(line, &self.word_manager)
+    }
+
+    /// Parses Forth code into a vector of instructions together with the span of each one.
+    /// The code may span several lines, the first one being `first_line`.
+    pub fn parse_with_spans(
+        &self,
+        input: String,
+        first_line: usize,
+    ) -> Result<(Vec<Instruction>, Vec<Span>), Error> {
+        self.current_parser()
+            .parse_with_spans(input, &self.word_manager, first_line)
+            .map_err(|error| error.with_source(self.source_name.as_deref()))
+    }
+
+    /// Returns the parser, reading number literals in the current base.
+    fn current_parser(&self) -> Parser {
+        self.parser.clone().with_base(self.handler.handle_base())
     }
 
     /// Checks if the stack is empty.
@@ -252,12 +828,40 @@ impl<W: Write> Forth<W> {
     }
 }
 
+/// Finds the index of the instruction that closes the control structure opened at `start`,
+/// skipping the nested ones. If it is not closed, the last instruction is returned,
+/// so that the structure is rejected as unbalanced when it is compiled.
+fn find_end_of_structure(data: &[Instruction], start: usize) -> usize {
+    let mut nesting_level = 0;
+    for (index, instruction) in data.iter().enumerate().skip(start) {
+        let Instruction::DefinitionType(definition_type) = instruction else {
+            continue;
+        };
+
+        if definition_type.opens_block() {
+            nesting_level += 1;
+        } else if definition_type.closes_block() {
+            nesting_level -= 1;
+            if nesting_level == 0 {
+                return index;
+            }
+        }
+    }
+    data.len().saturating_sub(1)
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::calculator::arithmetic_policy::ArithmeticPolicy;
+    use crate::calculator::calculator_errors::CalculatorError;
+    use crate::cell::CellSize;
+    use crate::errors::Error;
     use crate::forth::boolean_operations::{AND, GREATER_THAN, LESS_THAN};
+    use crate::forth::data_definition::{CONSTANT, TO, VALUE, VARIABLE};
     use crate::forth::interpreter::{DefinitionType, Forth, ForthError, Instruction, WordData};
     use crate::forth::output_instructions::{CR, DOT, EMIT, OutputInstruction};
     use crate::forth::word::WordType;
+    use crate::memory::memory_operations::{FETCH, STORE};
     use crate::stack::stack_operations::{DROP, DUP, OVER, ROT, SWAP};
     use std::io::Sink;
     #[test]
@@ -450,4 +1054,204 @@ mod tests {
 
         assert_eq!(result, expected_result);
     }
+
+    #[test]
+    fn can_store_and_fetch_a_variable() {
+        let mut forth: Forth<Sink> = Forth::new(None, None);
+        let data = vec![
+            Instruction::data_definition(VARIABLE, "x".to_string()),
+            Instruction::number(42),
+            Instruction::definition_type(DefinitionType::name("x")),
+            Instruction::memory_word(STORE),
+            Instruction::definition_type(DefinitionType::name("x")),
+            Instruction::memory_word(FETCH),
+        ];
+        let expected_result = [42];
+
+        assert_eq!(forth.process_instructions(data), Ok(()));
+        assert_eq!(forth.get_stack_content(), &expected_result);
+    }
+
+    #[test]
+    fn can_define_constants_and_change_values() {
+        let mut forth: Forth<Sink> = Forth::new(None, None);
+        let data = vec![
+            Instruction::number(7),
+            Instruction::data_definition(CONSTANT, "seven".to_string()),
+            Instruction::number(1),
+            Instruction::data_definition(VALUE, "v".to_string()),
+            Instruction::definition_type(DefinitionType::name("v")),
+            Instruction::number(5),
+            Instruction::data_definition(TO, "v".to_string()),
+            Instruction::definition_type(DefinitionType::name("seven")),
+            Instruction::definition_type(DefinitionType::name("v")),
+        ];
+        let expected_result = [1, 7, 5];
+
+        assert_eq!(forth.process_instructions(data), Ok(()));
+        assert_eq!(forth.get_stack_content(), &expected_result);
+    }
+
+    #[test]
+    fn cannot_change_a_word_that_is_not_a_value() {
+        let mut forth: Forth<Sink> = Forth::new(None, None);
+        let data = vec![
+            Instruction::number(1),
+            Instruction::data_definition(TO, "missing".to_string()),
+        ];
+
+        let result = forth.process_instructions(data);
+
+        assert_eq!(result, Err(ForthError::UnknownWord(None).into()));
+    }
+
+    #[test]
+    fn cannot_allot_beyond_the_configured_data_space() {
+        let mut forth: Forth<Sink> = Forth::new(None, None).with_data_space_size(Some(4));
+        let data = vec![
+            Instruction::data_definition(VARIABLE, "a".to_string()),
+            Instruction::data_definition(VARIABLE, "b".to_string()),
+            Instruction::data_definition(VARIABLE, "c".to_string()),
+        ];
+
+        let result = forth.process_instructions(data);
+
+        assert_eq!(result, Err(ForthError::DataSpaceOverflow.into()));
+    }
+
+    #[test]
+    fn runaway_recursion_exceeds_the_max_call_depth() {
+        let mut forth: Forth<Sink> = Forth::new(None, None).with_max_call_depth(Some(10));
+        let word = vec![
+            Instruction::start_definition(),
+            Instruction::definition_type(DefinitionType::name("forever")),
+            Instruction::definition_type(DefinitionType::Recurse),
+            Instruction::end_definition(),
+        ];
+        let data = vec![Instruction::definition_type(DefinitionType::name(
+            "forever",
+        ))];
+
+        let _ = forth.process_instructions(word);
+        let result = forth.process_instructions(data);
+
+        assert_eq!(result, Err(ForthError::MaxCallDepthExceeded.into()));
+    }
+
+    #[test]
+    fn wider_cells_do_not_overflow_with_realistic_arithmetic() {
+        let mut forth: Forth<Sink> = Forth::new(None, None).with_cell_size(CellSize::I32);
+        let instructions = forth.parse_instructions("1000 1000 * 40000 +".to_string());
+
+        let result = forth.process_instructions(instructions);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(forth.get_stack_content(), &vec![1040000]);
+    }
+
+    #[test]
+    fn narrow_cells_wrap_around_on_overflow() {
+        let mut forth: Forth<Sink> = Forth::new(None, None);
+        let instructions = forth.parse_instructions("32767 1 +".to_string());
+
+        let _ = forth.process_instructions(instructions);
+
+        assert_eq!(forth.get_stack_content(), &vec![-32768]);
+    }
+
+    #[test]
+    fn checked_arithmetic_reports_overflows() {
+        let mut forth: Forth<Sink> =
+            Forth::new(None, None).with_arithmetic_policy(ArithmeticPolicy::Checked);
+        let instructions = forth.parse_instructions("-32768 -1 /".to_string());
+
+        let result = forth.process_instructions(instructions);
+
+        assert_eq!(result, Err(CalculatorError::Overflow.into()));
+    }
+
+    #[test]
+    fn saturating_arithmetic_clamps_the_results() {
+        let mut forth: Forth<Sink> =
+            Forth::new(None, None).with_arithmetic_policy(ArithmeticPolicy::Saturating);
+        let instructions = forth.parse_instructions("32000 1000 + -32768 -1 /".to_string());
+
+        let _ = forth.process_instructions(instructions);
+
+        assert_eq!(forth.get_stack_content(), &vec![32767, 32767]);
+    }
+
+    #[test]
+    fn literals_are_read_in_the_base_set_before_them_on_the_same_line() {
+        let mut forth: Forth<Sink> = Forth::new(None, None);
+        let (instructions, spans) = forth
+            .parse_with_spans("16 base ! ff 10 2 base ! 1 if 11 then".to_string(), 1)
+            .unwrap();
+
+        let result = forth.process_instructions_with_spans(instructions, spans);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(forth.get_stack_content(), &vec![255, 16, 3]);
+    }
+
+    #[test]
+    fn the_base_takes_no_cell_of_the_data_space() {
+        let mut forth: Forth<Sink> = Forth::new(None, None);
+        let instructions = forth.parse_instructions("here hex base @ decimal".to_string());
+
+        let result = forth.process_instructions(instructions);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(forth.get_stack_content(), &vec![0, 16]);
+    }
+
+    #[test]
+    fn the_base_can_not_be_reached_through_other_addresses() {
+        let mut forth: Forth<Sink> = Forth::new(None, None);
+        let instructions = forth.parse_instructions("hex -1 @".to_string());
+
+        let result = forth.process_instructions(instructions);
+
+        assert_eq!(result, Err(ForthError::InvalidAddress.into()));
+        assert_eq!(forth.handler.handle_base(), 16);
+    }
+
+    #[test]
+    fn literals_that_are_not_numbers_in_the_current_base_are_unknown() {
+        let mut forth: Forth<Sink> = Forth::new(None, None);
+        let (instructions, spans) = forth.parse_with_spans("2 base ! 9".to_string(), 1).unwrap();
+
+        let result = forth
+            .process_instructions_with_spans(instructions, spans)
+            .map_err(Error::into_terse);
+
+        assert_eq!(result, Err(ForthError::UnknownWord(None).into()));
+    }
+
+    #[test]
+    fn literals_that_do_not_fit_in_a_cell_are_discarded() {
+        let mut forth: Forth<Sink> = Forth::new(None, None);
+        let lines = [": foo 40000 2 ;", "40000 1 foo", "16 base ! 40000 decimal"];
+
+        for line in lines {
+            let (instructions, spans) = forth.parse_with_spans(line.to_string(), 1).unwrap();
+            let result = forth.process_instructions_with_spans(instructions, spans);
+
+            assert_eq!(result, Ok(()));
+        }
+        assert_eq!(forth.get_stack_content(), &vec![1, 2]);
+    }
+
+    #[test]
+    fn strict_mode_reports_literals_that_do_not_fit_in_a_cell_after_changes_of_the_base() {
+        let mut forth: Forth<Sink> = Forth::new(None, None).with_strict_mode(true);
+        let line = "16 base ! 40000".to_string();
+        let (instructions, spans) = forth.parse_with_spans(line, 1).unwrap();
+
+        let result = forth
+            .process_instructions_with_spans(instructions, spans)
+            .map_err(Error::into_terse);
+
+        assert_eq!(result, Err(ForthError::UnknownWord(None).into()));
+    }
 }
@@ -1,15 +1,21 @@
+use crate::cell::Cell;
+use crate::memory::memory_operations::MemoryOperation;
+use crate::memory::string_operations::{StringKind, StringOperation};
 use crate::stack::stack_operations::StackOperation;
 
 use super::{
     boolean_operations::{BooleanOperation, LogicalOperation},
+    data_definition::DataDefinition,
     definition_type::DefinitionType,
+    dictionary_word::DictionaryWord,
     output_instructions::OutputInstruction,
 };
 
 /// Represents the different types of instructions that can be executed in the Forth interpreter
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Instruction {
-    Number(i16),
+    Number(Cell),
+    Literal(String),
     Operator(String),
     StackWord(StackOperation),
     StartDefinition,
@@ -19,13 +25,23 @@ pub enum Instruction {
     LogicalOperation(LogicalOperation),
     Output(OutputInstruction),
     OutputDotQuote(String),
+    MemoryWord(MemoryOperation),
+    DataDefinition(DataDefinition, String),
+    StackEffect(String),
+    StringWord(StringOperation),
+    StringLiteral(StringKind, Vec<u8>),
+    DictionaryWord(DictionaryWord, String),
 }
 
 impl Instruction {
-    pub fn number(value: i16) -> Self {
+    pub fn number(value: Cell) -> Self {
         Instruction::Number(value)
     }
 
+    pub fn literal(token: impl Into<String>) -> Self {
+        Instruction::Literal(token.into())
+    }
+
     pub fn operator(op: impl Into<String>) -> Self {
         Instruction::Operator(op.into())
     }
@@ -57,4 +73,28 @@ impl Instruction {
     pub fn output(output: OutputInstruction) -> Self {
         Instruction::Output(output)
     }
+
+    pub fn memory_word(op: MemoryOperation) -> Self {
+        Instruction::MemoryWord(op)
+    }
+
+    pub fn data_definition(definition: DataDefinition, name: impl Into<String>) -> Self {
+        Instruction::DataDefinition(definition, name.into())
+    }
+
+    pub fn stack_effect(text: impl Into<String>) -> Self {
+        Instruction::StackEffect(text.into())
+    }
+
+    pub fn string_word(op: StringOperation) -> Self {
+        Instruction::StringWord(op)
+    }
+
+    pub fn string_literal(kind: StringKind, text: impl Into<Vec<u8>>) -> Self {
+        Instruction::StringLiteral(kind, text.into())
+    }
+
+    pub fn dictionary_word(word: DictionaryWord, name: impl Into<String>) -> Self {
+        Instruction::DictionaryWord(word, name.into())
+    }
 }
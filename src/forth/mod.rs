@@ -1,9 +1,13 @@
 pub mod boolean_operations;
+pub mod data_definition;
 pub mod definition_type;
+pub mod dictionary_word;
 pub mod forth_errors;
 pub mod interpreter;
 pub mod intruction;
 pub mod output_instructions;
 pub mod parser;
+pub mod pictured_output;
+pub mod span;
 pub mod word;
 pub mod word_data;
@@ -2,6 +2,27 @@
 pub const DOT: OutputInstruction = OutputInstruction::Dot;
 pub const EMIT: OutputInstruction = OutputInstruction::Emit;
 pub const CR: OutputInstruction = OutputInstruction::CR;
+pub const DOT_S: OutputInstruction = OutputInstruction::DotS;
+pub const LESS_NUMBER_SIGN: OutputInstruction = OutputInstruction::LessNumberSign;
+pub const NUMBER_SIGN: OutputInstruction = OutputInstruction::NumberSign;
+pub const NUMBER_SIGN_S: OutputInstruction = OutputInstruction::NumberSignS;
+pub const HOLD: OutputInstruction = OutputInstruction::Hold;
+pub const SIGN: OutputInstruction = OutputInstruction::Sign;
+pub const NUMBER_SIGN_GREATER: OutputInstruction = OutputInstruction::NumberSignGreater;
+pub const TYPE: OutputInstruction = OutputInstruction::Type;
+pub const U_DOT: OutputInstruction = OutputInstruction::UDot;
+pub const DOT_R: OutputInstruction = OutputInstruction::DotR;
+pub const U_DOT_R: OutputInstruction = OutputInstruction::UDotR;
+pub const QUESTION: OutputInstruction = OutputInstruction::Question;
+pub const DUMP: OutputInstruction = OutputInstruction::Dump;
+pub const SPACE: OutputInstruction = OutputInstruction::Space;
+pub const SPACES: OutputInstruction = OutputInstruction::Spaces;
+
+/// Character pushed by `BL`: a blank space.
+pub const BLANK: char = ' ';
+
+/// Number of cells shown on each line by `DUMP`.
+pub const DUMP_LINE_CELLS: usize = 8;
 
 ///  Enum representing the different types of output instructions in Forth
 /// This includes instructions for dot, emit, carriage return (CR), and dot-quote
@@ -9,15 +30,58 @@ pub const CR: OutputInstruction = OutputInstruction::CR;
 /// The emit instruction is used to print a character.
 /// The CR instruction is used to print a newline.
 /// The dot-quote instruction is used to print a string.
-#[derive(Debug, PartialEq)]
+/// The dot-s instruction is used to print the whole stack without modifying it.
+/// The pictured numeric output instructions (`<#`, `#`, `#S`, `HOLD`, `SIGN` and `#>`)
+/// build a string from an unsigned double-cell number, which is printed with `TYPE`.
+/// The `U.` instruction prints the top item as an unsigned number, and `.R` and `U.R`
+/// print it right-aligned in a field of the given width.
+/// The `?` instruction prints the value stored at an address, and `DUMP` prints
+/// the values stored in a region of the data space, preceded by their addresses.
+/// The `SPACE` and `SPACES` instructions print one or the given number of spaces.
+/// The dot-paren instruction (`.(`) prints a string as it is, even while a word is compiled.
+#[derive(Debug, PartialEq, Clone)]
 pub enum OutputInstruction {
     Dot,
     Emit,
     CR,
     DotQuote(String),
+    DotS,
+    LessNumberSign,
+    NumberSign,
+    NumberSignS,
+    Hold,
+    Sign,
+    NumberSignGreater,
+    Type,
+    UDot,
+    DotR,
+    UDotR,
+    Question,
+    Dump,
+    Space,
+    Spaces,
+    DotParen(String),
 }
 impl OutputInstruction {
     pub fn dot_quote(content: impl Into<String>) -> Self {
         OutputInstruction::DotQuote(content.into())
     }
+
+    pub fn dot_paren(content: impl Into<String>) -> Self {
+        OutputInstruction::DotParen(content.into())
+    }
+}
+
+/// Spacing of the output of `EMIT` and `."`.
+/// - Legacy: They are followed by a space, as in the original interpreter
+///   (`72 EMIT 105 EMIT` prints `H i `). Kept as the default for existing expected outputs.
+/// - Standard: They print exactly their characters, as in standard Forth
+///   (`72 EMIT 105 EMIT` prints `Hi`).
+///
+/// `.` and `U.` are followed by a space in both modes, as in standard Forth.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum OutputMode {
+    #[default]
+    Legacy,
+    Standard,
 }
@@ -1,11 +1,39 @@
-use crate::errors::Error;
-
-use super::boolean_operations::{AND, EQUAL, GREATER_THAN, LESS_THAN, NOT, OR};
-use super::definition_type::{DefinitionType, ELSE, IF, THEN};
-use super::output_instructions::{CR, DOT, EMIT, OutputInstruction};
-use super::word::{WordDefinitionManager, WordType};
+use crate::calculator::arithmetic_policy::ArithmeticPolicy;
+use crate::calculator::division_mode::DivisionMode;
+use crate::cell::{
+    BINARY_BASE, Cell, CellSize, DECIMAL_BASE, HEX_BASE, is_base_independent, is_literal,
+};
+use crate::errors::{Error, ErrorFormat, Location};
+
+use super::boolean_operations::{
+    AND, ARSHIFT, BooleanMode, EQUAL, GREATER_OR_EQUAL, GREATER_THAN, INVERT, LESS_OR_EQUAL,
+    LESS_THAN, LSHIFT, NOT, NOT_EQUAL, OR, RSHIFT, U_GREATER_THAN, U_LESS_THAN, WITHIN, XOR,
+    ZERO_EQUAL, ZERO_GREATER, ZERO_LESS, ZERO_NOT_EQUAL,
+};
+use super::data_definition::{CONSTANT, CREATE, DataDefinition, TO, VALUE, VARIABLE};
+use super::definition_type::{
+    AGAIN, BEGIN, DO, DefinitionType, ELSE, EXIT, IF, LEAVE, LOOP, LOOP_INDEX, OUTER_LOOP_INDEX,
+    PLUS_LOOP, RECURSE, REPEAT, THEN, UNLOOP, UNTIL, WHILE,
+};
+use super::dictionary_word::{DictionaryWord, FORGET, MARKER, SEE, WORDS};
+use super::forth_errors::{ForthError, UnknownToken};
+use super::output_instructions::{
+    BLANK, CR, DOT, DOT_R, DOT_S, DUMP, EMIT, HOLD, LESS_NUMBER_SIGN, NUMBER_SIGN,
+    NUMBER_SIGN_GREATER, NUMBER_SIGN_S, OutputInstruction, OutputMode, QUESTION, SIGN, SPACE,
+    SPACES, TYPE, U_DOT, U_DOT_R,
+};
+use super::span::Span;
+use super::word::{CaseSensitivity, WordDefinitionManager, WordType};
 use crate::forth::intruction::Instruction;
-use crate::stack::stack_operations::{DROP, DUP, OVER, ROT, SWAP};
+use crate::memory::memory_operations::{
+    ALLOT, BASE, BINARY, CELL_PLUS, CELLS, CHAR_FETCH, CHAR_STORE, COMMA, DECIMAL, FETCH, HERE,
+    HEX, MemoryOperation, PLUS_STORE, STORE,
+};
+use crate::memory::string_operations::{C_QUOTE, COMPARE, COUNT, S_QUOTE, SEARCH, SLASH_STRING};
+use crate::stack::stack_operations::{
+    DEPTH, DROP, DUP, MINUS_ROT, NIP, OVER, PICK, QUESTION_DUP, R_FETCH, R_FROM, ROLL, ROT, SWAP,
+    TO_R, TUCK, TWO_DROP, TWO_DUP, TWO_OVER, TWO_R_FROM, TWO_SWAP, TWO_TO_R,
+};
 
 /// ParserState enum to represent the state of the parser
 /// This enum is used to track whether the parser is currently inside a definition,
@@ -25,8 +53,19 @@ pub enum ParserState {
 
 /// Parser for Forth instructions
 /// This struct is responsible for parsing Forth instructions from a string input.
-#[derive(Debug, PartialEq)]
-pub struct Parser {}
+/// In strict mode, undefined words inside definitions and number literals that do not fit
+/// in a cell are reported as errors instead of being discarded.
+/// Number literals are parsed according to the width of the cells and the numeric base.
+/// `HEX`, `DECIMAL` and `BINARY` outside definitions change the base of the literals that follow.
+/// Other changes of `BASE`, made by `BASE` itself, by words or inside control structures,
+/// can not be known while parsing, so the literals after them are kept as their tokens
+/// for the interpreter to read them in the base that is current when they are run.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parser {
+    strict: bool,
+    cell_size: CellSize,
+    base: u32,
+}
 
 impl Default for Parser {
     fn default() -> Self {
@@ -36,7 +75,57 @@ impl Default for Parser {
 
 impl Parser {
     pub fn new() -> Self {
-        Parser {}
+        Parser {
+            strict: false,
+            cell_size: CellSize::default(),
+            base: DECIMAL_BASE,
+        }
+    }
+
+    /// Enables or disables the strict compile mode.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    /// let parser = Parser::new().with_strict_mode(true);
+    /// ```
+    pub fn with_strict_mode(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Checks if the strict compile mode is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets the width of the cells, which limits the number literals that can be parsed.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::cell::CellSize;
+    /// let parser = Parser::new().with_cell_size(CellSize::I32);
+    /// ```
+    pub fn with_cell_size(mut self, cell_size: CellSize) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Sets the numeric base in which the number literals start being read.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::forth::intruction::Instruction;
+    ///# use rust_forth::forth::word::WordDefinitionManager;
+    /// let parser = Parser::new().with_base(16);
+    /// let word_manager = WordDefinitionManager::new();
+    ///
+    /// let result = parser.parse_instructions("ff".to_string(), &word_manager);
+    ///
+    /// assert_eq!(result, vec![Instruction::number(255)]);
+    /// ```
+    pub fn with_base(mut self, base: u32) -> Self {
+        self.base = base;
+        self
     }
 
     /// Parses a string input into a vector of Forth instructions.
@@ -65,73 +154,251 @@ impl Parser {
         input: String,
         word_manager: &WordDefinitionManager,
     ) -> Vec<Instruction> {
+        self.parse(input, word_manager, false, 1)
+            .map(|(instructions, _)| instructions)
+            .unwrap_or_default()
+    }
+
+    /// Parses a string input into a vector of Forth instructions, checking it if the
+    /// strict compile mode is enabled.
+    /// Returns `ForthError::UnknownWord` with the offending token and its column when
+    /// a definition uses an undefined word or a number literal does not fit in a cell.
+    /// The error is not located; use `parse_with_spans` to get its line too.
+    /// # Arguments
+    /// * `input` - A string containing the Forth instructions to be parsed.
+    /// * `word_manager` - The manager used to check if a word is defined.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::forth::word::WordDefinitionManager;
+    ///# use rust_forth::forth::forth_errors::{ForthError, UnknownToken};
+    /// let parser = Parser::new().with_strict_mode(true);
+    /// let word_manager = WordDefinitionManager::new();
+    /// let result = parser.try_parse_instructions(": foo dupp ;".to_string(), &word_manager);
+    /// let expected_error = ForthError::UnknownWord(Some(UnknownToken::new("dupp", 7)));
+    /// assert_eq!(result, Err(expected_error.into()));
+    /// ```
+    pub fn try_parse_instructions(
+        &self,
+        input: String,
+        word_manager: &WordDefinitionManager,
+    ) -> Result<Vec<Instruction>, Error> {
+        let (instructions, _) = self
+            .parse(input, word_manager, self.strict, 1)
+            .map_err(Error::into_terse)?;
+        Ok(instructions)
+    }
+
+    /// Parses a string input into a vector of Forth instructions, together with the span
+    /// of the token that produced each instruction.
+    /// The input may span several lines, the first one being `first_line`.
+    /// Errors found by the strict compile mode are located at the offending token.
+    /// # Arguments
+    /// * `input` - A string containing the Forth instructions to be parsed.
+    /// * `word_manager` - The manager used to check if a word is defined.
+    /// * `first_line` - The number of the line where the input starts.
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::forth::span::Span;
+    ///# use rust_forth::forth::word::WordDefinitionManager;
+    /// let parser = Parser::new();
+    /// let word_manager = WordDefinitionManager::new();
+    /// let input = String::from(": foo\n  1 + ;");
+    ///
+    /// let (_, spans) = parser.parse_with_spans(input, &word_manager, 3).unwrap();
+    ///
+    /// assert_eq!(spans[2], Span::new(4, 3, "1"));
+    /// ```
+    pub fn parse_with_spans(
+        &self,
+        input: String,
+        word_manager: &WordDefinitionManager,
+        first_line: usize,
+    ) -> Result<(Vec<Instruction>, Vec<Span>), Error> {
+        self.parse(input, word_manager, self.strict, first_line)
+    }
+
+    /// Parses the input, checking every token when `strict` is enabled.
+    /// Words defined earlier in the same input, as well as the word being defined, are accepted.
+    fn parse(
+        &self,
+        input: String,
+        word_manager: &WordDefinitionManager,
+        strict: bool,
+        first_line: usize,
+    ) -> Result<(Vec<Instruction>, Vec<Span>), Error> {
         let mut instructions = Vec::new();
-        let tokens = self.tokenize(&input);
+        let mut spans = Vec::new();
+        let mut tokens = self.tokenize(&input, first_line)?.into_iter();
         let mut state = ParserState::OutsideDefinition;
+        let mut line_definitions = Vec::new();
+        let mut base = self.base;
+        let mut base_known = true;
+
+        while let Some(span) = tokens.next() {
+            let token = span.word.to_string();
+            if let Some(comment) = parenthesised_comment(&token) {
+                if state == ParserState::InsideDefinition && follows_word_name(&instructions) {
+                    instructions.push(Instruction::stack_effect(comment));
+                    spans.push(span);
+                }
+                continue;
+            }
+            if state != ParserState::ParsingWordName
+                && let Some(definition) = self.parse_data_definition(&token)
+            {
+                let Some(name) = tokens.next().map(|name| name.word) else {
+                    return Err(missing_name_error(span));
+                };
+                line_definitions.push(word_manager.fold_name(&name));
+                instructions.push(Instruction::data_definition(definition, name));
+                spans.push(span);
+                continue;
+            }
+            if state != ParserState::ParsingWordName
+                && let Some(word) = self.parse_dictionary_word(&token)
+            {
+                let name = match word.takes_name() {
+                    true => match tokens.next() {
+                        Some(name) => name.word,
+                        None => return Err(missing_name_error(span)),
+                    },
+                    false => String::new(),
+                };
+                if word == MARKER {
+                    line_definitions.push(word_manager.fold_name(&name));
+                }
+                instructions.push(Instruction::dictionary_word(word, name));
+                spans.push(span);
+                continue;
+            }
+            if state == ParserState::ParsingWordName {
+                line_definitions.push(word_manager.fold_name(&token));
+            }
+            if strict
+                && base_known
+                && self.is_number(&token, base, word_manager)
+                && self.cell_size.parse_in_base(&token, base).is_none()
+            {
+                return Err(unknown_token_error(span));
+            }
+
+            let inside_definition = state == ParserState::InsideDefinition;
+            let interpreted = state == ParserState::OutsideDefinition;
+            let parsed_before = instructions.len();
+            self.parse_token(
+                token,
+                &mut instructions,
+                &mut state,
+                word_manager,
+                base,
+                base_known,
+            );
+
+            if !inside_definition && let Some(Instruction::MemoryWord(word)) = instructions.last() {
+                base = match word {
+                    MemoryOperation::Hex => HEX_BASE,
+                    MemoryOperation::Decimal => DECIMAL_BASE,
+                    MemoryOperation::Binary => BINARY_BASE,
+                    _ => base,
+                };
+            }
+            if interpreted && instructions.len() > parsed_before {
+                base_known &= !may_change_base(&instructions[parsed_before]);
+            }
 
-        for token in tokens {
-            self.parse_token(token, &mut instructions, &mut state, word_manager);
+            if strict
+                && inside_definition
+                && let Some(Instruction::DefinitionType(DefinitionType::Name(name))) =
+                    instructions.get(parsed_before)
+                && !word_manager.is_word_defined(&WordType::UserDefined(name.to_string()))
+                && !line_definitions.contains(&word_manager.fold_name(name))
+            {
+                return Err(unknown_token_error(span));
+            }
+            spans.resize(instructions.len(), span);
         }
 
-        instructions
+        Ok((instructions, spans))
     }
 
     /// Tokenizes the input string into a vector of tokens.
-    /// It splits the input string by whitespace and special characters, handling quoted strings separately.
-    /// Returns a vector of tokens.
+    /// It splits the input string by whitespace and special characters, handling quoted strings separately:
+    /// a string word (`."`, `S"`, `S\"`, `C"`, `.(`) and its text are kept as a single token,
+    /// and the text may span several lines.
+    /// Comments are skipped, except the parenthesised ones that are closed, which are kept
+    /// as a single token so that the stack effect of a definition can be read.
+    /// Returns a vector of tokens, each one with the line and column (starting at 1) where it begins,
+    /// or `ForthError::UnterminatedString`, located at the string word, if a text is not closed.
     ///
     /// # Arguments
     /// * `input` - A string containing the input to be tokenized.
-    fn tokenize(&self, input: &str) -> Vec<String> {
-        let mut tokens = Vec::new();
-        let mut in_quotes = false;
+    /// * `first_line` - The number of the line where the input starts.
+    fn tokenize(&self, input: &str, first_line: usize) -> Result<Vec<Span>, Error> {
+        let mut tokens: Vec<(String, usize)> = Vec::new();
+        let mut unterminated = None;
         let mut start = 0;
         let chars: Vec<char> = input.chars().collect();
+        let text = |from: usize, to: usize| chars[from..to].iter().collect::<String>();
 
         let mut i = 0;
         while i < chars.len() {
-            if chars[i] == '.' && input[i..].starts_with(".\" ") {
-                if start < i {
-                    tokens.push(input[start..i].to_string());
+            if start == i
+                && let Some((comment, end)) = find_comment(&chars, i)
+            {
+                if comment == Comment::Parenthesised {
+                    tokens.push((text(i, end), i));
                 }
+                i = end;
                 start = i;
-                i += 2;
-                in_quotes = true;
-
-                while i < chars.len() && chars[i] != '"' {
-                    i += 1;
-                }
-
-                if i < chars.len() && chars[i] == '"' {
-                    tokens.push(input[start..=i].to_string());
-                    i += 1;
-                }
-                if in_quotes {
-                    in_quotes = false;
+            } else if start == i
+                && let Some((end, closed)) = find_string(&chars, i)
+            {
+                if closed {
+                    tokens.push((text(i, end), i));
+                } else {
+                    let word_length = string_word_at(&chars, i).map_or(1, |word| word.length);
+                    unterminated = Some((text(i, i + word_length), i));
                 }
+                i = end;
                 start = i;
-            } else if chars[i].is_whitespace() && !in_quotes {
+            } else if chars[i].is_whitespace() {
                 if start < i {
-                    tokens.push(input[start..i].to_string());
+                    tokens.push((text(start, i), start));
                 }
                 start = i + 1;
                 i += 1;
-            } else if !in_quotes && matches!(chars[i], ':' | ';') {
+            } else if matches!(chars[i], ':' | ';') {
                 if start < i {
-                    tokens.push(input[start..i].to_string());
-                    println!("actual tokens: {:?}", tokens);
+                    tokens.push((text(start, i), start));
                 }
-                tokens.push(input[i..=i].to_string());
+                tokens.push((text(i, i + 1), i));
                 start = i + 1;
                 i += 1;
             } else {
                 i += 1;
             }
         }
-        if start < input.len() {
-            tokens.push(input[start..].to_string());
+        if start < chars.len() {
+            tokens.push((text(start, chars.len()), start));
+        }
+
+        let line_starts: Vec<usize> = chars
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == '\n')
+            .map(|(index, _)| index + 1)
+            .collect();
+        let to_span = |(token, offset): (String, usize)| {
+            let line = line_starts.partition_point(|&line_start| line_start <= offset);
+            let line_start = if line == 0 { 0 } else { line_starts[line - 1] };
+            Span::new(first_line + line, offset - line_start + 1, token)
+        };
+        if let Some(word) = unterminated {
+            return Err(unterminated_string_error(to_span(word)));
         }
-        tokens
+        Ok(tokens.into_iter().map(to_span).collect())
     }
 
     /// Parses a token into a Forth instruction.
@@ -144,12 +411,15 @@ impl Parser {
     /// - `instructions` - A mutable reference to a vector of Forth instructions where the parsed instruction will be added.
     /// - `state` - A mutable reference to the current parser state.
     /// - `word_manager` - A reference to the WordDefinitionManager instance used to check if a word is defined.
+    /// - `base` - The numeric base in which number literals are read.
     fn parse_token(
         &self,
         token: String,
         instructions: &mut Vec<Instruction>,
         state: &mut ParserState,
         word_manager: &WordDefinitionManager,
+        base: u32,
+        base_known: bool,
     ) {
         match state {
             ParserState::OutsideDefinition => match token.as_str() {
@@ -158,29 +428,29 @@ impl Parser {
                     *state = ParserState::ParsingWordName;
                 }
                 ";" => instructions.push(Instruction::end_definition()),
-                "." => instructions.push(Instruction::output(DOT)),
-                _ if token.eq_ignore_ascii_case("emit") => {
-                    instructions.push(Instruction::output(EMIT));
-                }
-                _ if token.eq_ignore_ascii_case("cr") => instructions.push(Instruction::output(CR)),
-                _ if token.starts_with('.') && token.ends_with('"') => {
-                    let quoted_string = &token[3..token.len() - 1];
-                    instructions.push(Instruction::output(OutputInstruction::dot_quote(
-                        quoted_string.to_string(),
-                    )));
+                _ if self
+                    .parse_output_instruction(&token, word_manager)
+                    .is_some() =>
+                {
+                    if let Some(output) = self.parse_output_instruction(&token, word_manager) {
+                        instructions.push(output);
+                    }
                 }
-                _ if self.is_number(token.to_string()) => {
-                    if let Ok(parsed_num) = token.parse::<i16>() {
-                        instructions.push(Instruction::number(parsed_num));
+                _ if self.parse_string_word(&token).is_some() => {
+                    if let Some(string_word) = self.parse_string_word(&token) {
+                        instructions.push(string_word);
                     }
                 }
+                _ if self.is_number(&token, base, word_manager) => {
+                    instructions.extend(self.parse_number(token, base, base_known));
+                }
                 _ if self.is_operator(token.to_string()) => {
                     if word_manager.is_word_defined(&WordType::UserDefined(token.to_string())) {
                         instructions.push(Instruction::definition_type(DefinitionType::Name(
-                            token.to_lowercase().to_string(),
+                            token.to_string(),
                         )));
                     } else {
-                        instructions.push(Instruction::operator(token));
+                        instructions.push(Instruction::operator(token.to_lowercase()));
                     }
                 }
                 _ if self.parse_stack_operation(&token, word_manager).is_some() => {
@@ -188,6 +458,16 @@ impl Parser {
                         instructions.push(stack_op);
                     }
                 }
+                _ if self.parse_memory_operation(&token, word_manager).is_some() => {
+                    if let Some(memory_op) = self.parse_memory_operation(&token, word_manager) {
+                        instructions.push(memory_op);
+                    }
+                }
+                _ if self.parse_string_operation(&token, word_manager).is_some() => {
+                    if let Some(string_op) = self.parse_string_operation(&token, word_manager) {
+                        instructions.push(string_op);
+                    }
+                }
                 _ if self.parse_logical_operation(&token).is_some() => {
                     if let Some(logical_op) = self.parse_logical_operation(&token) {
                         instructions.push(logical_op);
@@ -214,29 +494,29 @@ impl Parser {
                     instructions.push(Instruction::end_definition());
                     *state = ParserState::OutsideDefinition;
                 }
-                "." => instructions.push(Instruction::output(DOT)),
-                _ if token.eq_ignore_ascii_case("emit") => {
-                    instructions.push(Instruction::output(EMIT));
-                }
-                _ if token.eq_ignore_ascii_case("cr") => instructions.push(Instruction::output(CR)),
-                _ if token.starts_with('.') && token.ends_with('"') => {
-                    let quoted_string = &token[3..token.len() - 1];
-                    instructions.push(Instruction::output(OutputInstruction::dot_quote(
-                        quoted_string.to_string(),
-                    )));
+                _ if self
+                    .parse_output_instruction(&token, word_manager)
+                    .is_some() =>
+                {
+                    if let Some(output) = self.parse_output_instruction(&token, word_manager) {
+                        instructions.push(output);
+                    }
                 }
-                _ if self.is_number(token.to_string()) => {
-                    if let Ok(parsed_num) = token.parse::<i16>() {
-                        instructions.push(Instruction::number(parsed_num));
+                _ if self.parse_string_word(&token).is_some() => {
+                    if let Some(string_word) = self.parse_string_word(&token) {
+                        instructions.push(string_word);
                     }
                 }
+                _ if self.is_number(&token, base, word_manager) => {
+                    instructions.extend(self.parse_number(token, base, base_known));
+                }
                 _ if self.is_operator(token.to_string()) => {
                     if word_manager.is_word_defined(&WordType::UserDefined(token.to_string())) {
                         instructions.push(Instruction::definition_type(DefinitionType::Name(
-                            token.to_lowercase().to_string(),
+                            token.to_string(),
                         )));
                     } else {
-                        instructions.push(Instruction::operator(token));
+                        instructions.push(Instruction::operator(token.to_lowercase()));
                     }
                 }
                 _ if self.parse_logical_operation(&token).is_some() => {
@@ -254,6 +534,16 @@ impl Parser {
                         instructions.push(stack_op);
                     }
                 }
+                _ if self.parse_memory_operation(&token, word_manager).is_some() => {
+                    if let Some(memory_op) = self.parse_memory_operation(&token, word_manager) {
+                        instructions.push(memory_op);
+                    }
+                }
+                _ if self.parse_string_operation(&token, word_manager).is_some() => {
+                    if let Some(string_op) = self.parse_string_operation(&token, word_manager) {
+                        instructions.push(string_op);
+                    }
+                }
                 _ => {
                     if let Some(word) = self.parse_word(&token, word_manager) {
                         instructions.push(word);
@@ -264,29 +554,121 @@ impl Parser {
     }
 
     /// Checks if a token is a number.
-    /// It checks if the token is a valid number, including negative numbers.
+    /// It checks if the token is a valid number in the given base, including negative numbers,
+    /// prefixed literals such as `$FF` and character literals such as `'A'`.
+    /// Words take precedence, so words such as `add` or `dup` can still be used in other bases.
     /// # Arguments
     /// - `token` - A string containing the token to be checked.
-    fn is_number(&self, token: String) -> bool {
-        if token.is_empty() {
-            return false;
-        }
-        let chars: Vec<char> = token.chars().collect();
-        if chars[0] == '-' {
-            return chars.len() > 1 && chars[1..].iter().all(|c| c.is_ascii_digit());
+    /// - `base` - The numeric base in which the token is read.
+    /// - `word_manager` - The manager used to check if a word is defined.
+    fn is_number(&self, token: &str, base: u32, word_manager: &WordDefinitionManager) -> bool {
+        is_literal(token, base) && !self.is_word(token, word_manager)
+    }
+
+    /// Parses a number literal in `base`, discarding it if it does not fit in a cell.
+    /// When the base may have changed before the literal is run (`base_known` is false),
+    /// literals that depend on it are kept as their tokens, for the interpreter to read them
+    /// in the base that is current then.
+    fn parse_number(&self, token: String, base: u32, base_known: bool) -> Option<Instruction> {
+        if !base_known && !is_base_independent(&token) {
+            return Some(Instruction::literal(token));
         }
-        chars.iter().all(|c| c.is_ascii_digit())
+        self.cell_size
+            .parse_in_base(&token, base)
+            .map(Instruction::number)
+    }
+
+    /// Checks if a token is a built-in or user-defined word.
+    fn is_word(&self, token: &str, word_manager: &WordDefinitionManager) -> bool {
+        let is_control_word = !matches!(
+            self.parse_word(&token.to_string(), word_manager),
+            Some(Instruction::DefinitionType(DefinitionType::Name(_)))
+        );
+        word_manager.is_word_defined(&WordType::UserDefined(token.to_string()))
+            || is_control_word
+            || self.parse_output_instruction(token, word_manager).is_some()
+            || self.is_operator(token.to_string())
+            || self.parse_stack_operation(token, word_manager).is_some()
+            || self.parse_memory_operation(token, word_manager).is_some()
+            || self.parse_string_operation(token, word_manager).is_some()
+            || self.parse_logical_operation(token).is_some()
+            || self.parse_boolean_operation(token).is_some()
+            || self.parse_data_definition(token).is_some()
+            || self.parse_dictionary_word(token).is_some()
     }
 
     /// Checks if a token is an operator.
-    /// It matches the token against a set of known operators.
-    /// Currently, it checks for the following operators: "+", "-", "*", "/".
+    /// It matches the token, regardless of its case, against the arithmetic word set:
+    /// "+", "-", "*", "/", "MOD", "/MOD", "*/", "*/MOD", "NEGATE", "ABS", "MIN", "MAX",
+    /// "1+", "1-", "2*" and "2/".
     ///
     /// # Arguments
     ///
     /// - `token` - A string containing the token to be checked.
     fn is_operator(&self, token: String) -> bool {
-        matches!(token.as_str(), "+" | "-" | "*" | "/")
+        matches!(
+            token.to_lowercase().as_str(),
+            "+" | "-"
+                | "*"
+                | "/"
+                | "mod"
+                | "/mod"
+                | "*/"
+                | "*/mod"
+                | "negate"
+                | "abs"
+                | "min"
+                | "max"
+                | "1+"
+                | "1-"
+                | "2*"
+                | "2/"
+        )
+    }
+
+    /// Parses a token into an output instruction, other than `."`.
+    /// `BL`, which pushes the blank character used by `EMIT`, is parsed here too.
+    /// User-defined words take precedence over every output word but `.`, `EMIT`, `CR` and `.S`.
+    ///
+    /// # Arguments
+    ///
+    /// - `token` - A string containing the token to be parsed.
+    /// - `word_manager` - The manager used to check if a word is defined.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(Instruction)` if the token is an output instruction.
+    /// - `None` if the token is not an output instruction.
+    fn parse_output_instruction(
+        &self,
+        token: &str,
+        word_manager: &WordDefinitionManager,
+    ) -> Option<Instruction> {
+        match token {
+            "." => Some(Instruction::output(DOT)),
+            _ if token.eq_ignore_ascii_case("emit") => Some(Instruction::output(EMIT)),
+            _ if token.eq_ignore_ascii_case("cr") => Some(Instruction::output(CR)),
+            _ if token.eq_ignore_ascii_case(".s") => Some(Instruction::output(DOT_S)),
+            _ if word_manager.is_word_defined(&WordType::UserDefined(token.to_string())) => Some(
+                Instruction::DefinitionType(DefinitionType::Name(token.to_string())),
+            ),
+            "<#" => Some(Instruction::output(LESS_NUMBER_SIGN)),
+            "#" => Some(Instruction::output(NUMBER_SIGN)),
+            "#>" => Some(Instruction::output(NUMBER_SIGN_GREATER)),
+            _ if token.eq_ignore_ascii_case("#s") => Some(Instruction::output(NUMBER_SIGN_S)),
+            _ if token.eq_ignore_ascii_case("hold") => Some(Instruction::output(HOLD)),
+            _ if token.eq_ignore_ascii_case("sign") => Some(Instruction::output(SIGN)),
+            _ if token.eq_ignore_ascii_case("type") => Some(Instruction::output(TYPE)),
+            _ if token.eq_ignore_ascii_case("u.") => Some(Instruction::output(U_DOT)),
+            _ if token.eq_ignore_ascii_case(".r") => Some(Instruction::output(DOT_R)),
+            _ if token.eq_ignore_ascii_case("u.r") => Some(Instruction::output(U_DOT_R)),
+            "?" => Some(Instruction::output(QUESTION)),
+            _ if token.eq_ignore_ascii_case("dump") => Some(Instruction::output(DUMP)),
+            _ if token.eq_ignore_ascii_case("space") => Some(Instruction::output(SPACE)),
+            _ if token.eq_ignore_ascii_case("spaces") => Some(Instruction::output(SPACES)),
+            _ if token.eq_ignore_ascii_case("bl") => Some(Instruction::number(BLANK as Cell)),
+            _ => None,
+        }
     }
 
     /// Parses a token into a logical operation.
@@ -307,6 +689,36 @@ impl Parser {
                 Some(Instruction::logical_operation(GREATER_THAN))
             }
             _ if token.eq_ignore_ascii_case("=") => Some(Instruction::logical_operation(EQUAL)),
+            _ if token.eq_ignore_ascii_case("<>") => {
+                Some(Instruction::logical_operation(NOT_EQUAL))
+            }
+            _ if token.eq_ignore_ascii_case("<=") => {
+                Some(Instruction::logical_operation(LESS_OR_EQUAL))
+            }
+            _ if token.eq_ignore_ascii_case(">=") => {
+                Some(Instruction::logical_operation(GREATER_OR_EQUAL))
+            }
+            _ if token.eq_ignore_ascii_case("0=") => {
+                Some(Instruction::logical_operation(ZERO_EQUAL))
+            }
+            _ if token.eq_ignore_ascii_case("0<") => {
+                Some(Instruction::logical_operation(ZERO_LESS))
+            }
+            _ if token.eq_ignore_ascii_case("0>") => {
+                Some(Instruction::logical_operation(ZERO_GREATER))
+            }
+            _ if token.eq_ignore_ascii_case("0<>") => {
+                Some(Instruction::logical_operation(ZERO_NOT_EQUAL))
+            }
+            _ if token.eq_ignore_ascii_case("u<") => {
+                Some(Instruction::logical_operation(U_LESS_THAN))
+            }
+            _ if token.eq_ignore_ascii_case("u>") => {
+                Some(Instruction::logical_operation(U_GREATER_THAN))
+            }
+            _ if token.eq_ignore_ascii_case("within") => {
+                Some(Instruction::logical_operation(WITHIN))
+            }
             _ => None,
         }
     }
@@ -326,6 +738,19 @@ impl Parser {
             _ if token.eq_ignore_ascii_case("and") => Some(Instruction::boolean_operation(AND)),
             _ if token.eq_ignore_ascii_case("or") => Some(Instruction::boolean_operation(OR)),
             _ if token.eq_ignore_ascii_case("not") => Some(Instruction::boolean_operation(NOT)),
+            _ if token.eq_ignore_ascii_case("xor") => Some(Instruction::boolean_operation(XOR)),
+            _ if token.eq_ignore_ascii_case("invert") => {
+                Some(Instruction::boolean_operation(INVERT))
+            }
+            _ if token.eq_ignore_ascii_case("lshift") => {
+                Some(Instruction::boolean_operation(LSHIFT))
+            }
+            _ if token.eq_ignore_ascii_case("rshift") => {
+                Some(Instruction::boolean_operation(RSHIFT))
+            }
+            _ if token.eq_ignore_ascii_case("arshift") => {
+                Some(Instruction::boolean_operation(ARSHIFT))
+            }
             _ => None,
         }
     }
@@ -358,6 +783,152 @@ impl Parser {
             _ if token.eq_ignore_ascii_case("swap") => Some(Instruction::stack_word(SWAP)),
             _ if token.eq_ignore_ascii_case("over") => Some(Instruction::stack_word(OVER)),
             _ if token.eq_ignore_ascii_case("rot") => Some(Instruction::stack_word(ROT)),
+            _ if token.eq_ignore_ascii_case("-rot") => Some(Instruction::stack_word(MINUS_ROT)),
+            _ if token.eq_ignore_ascii_case("nip") => Some(Instruction::stack_word(NIP)),
+            _ if token.eq_ignore_ascii_case("tuck") => Some(Instruction::stack_word(TUCK)),
+            _ if token.eq_ignore_ascii_case("pick") => Some(Instruction::stack_word(PICK)),
+            _ if token.eq_ignore_ascii_case("roll") => Some(Instruction::stack_word(ROLL)),
+            _ if token.eq_ignore_ascii_case("?dup") => Some(Instruction::stack_word(QUESTION_DUP)),
+            _ if token.eq_ignore_ascii_case("2dup") => Some(Instruction::stack_word(TWO_DUP)),
+            _ if token.eq_ignore_ascii_case("2drop") => Some(Instruction::stack_word(TWO_DROP)),
+            _ if token.eq_ignore_ascii_case("2swap") => Some(Instruction::stack_word(TWO_SWAP)),
+            _ if token.eq_ignore_ascii_case("2over") => Some(Instruction::stack_word(TWO_OVER)),
+            _ if token.eq_ignore_ascii_case("depth") => Some(Instruction::stack_word(DEPTH)),
+            _ if token.eq_ignore_ascii_case(">r") => Some(Instruction::stack_word(TO_R)),
+            _ if token.eq_ignore_ascii_case("r>") => Some(Instruction::stack_word(R_FROM)),
+            _ if token.eq_ignore_ascii_case("r@") => Some(Instruction::stack_word(R_FETCH)),
+            _ if token.eq_ignore_ascii_case("2>r") => Some(Instruction::stack_word(TWO_TO_R)),
+            _ if token.eq_ignore_ascii_case("2r>") => Some(Instruction::stack_word(TWO_R_FROM)),
+            _ => None,
+        }
+    }
+
+    /// Parses a token into a memory operation.
+    /// It checks if the token is a word that accesses the data space and creates the
+    /// corresponding Forth instruction. User-defined words take precedence.
+    ///
+    /// # Arguments
+    ///
+    /// - `token` - A string containing the token to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(Instruction)` if the token is a memory operation.
+    /// - `None` if the token is not a memory operation.
+    fn parse_memory_operation(
+        &self,
+        token: &str,
+        word_manager: &WordDefinitionManager,
+    ) -> Option<Instruction> {
+        if word_manager.is_word_defined(&WordType::UserDefined(token.to_string())) {
+            return Some(Instruction::DefinitionType(DefinitionType::Name(
+                token.to_string(),
+            )));
+        }
+
+        match token {
+            "@" => Some(Instruction::memory_word(FETCH)),
+            "!" => Some(Instruction::memory_word(STORE)),
+            "+!" => Some(Instruction::memory_word(PLUS_STORE)),
+            "," => Some(Instruction::memory_word(COMMA)),
+            _ if token.eq_ignore_ascii_case("c@") => Some(Instruction::memory_word(CHAR_FETCH)),
+            _ if token.eq_ignore_ascii_case("c!") => Some(Instruction::memory_word(CHAR_STORE)),
+            _ if token.eq_ignore_ascii_case("here") => Some(Instruction::memory_word(HERE)),
+            _ if token.eq_ignore_ascii_case("allot") => Some(Instruction::memory_word(ALLOT)),
+            _ if token.eq_ignore_ascii_case("cells") => Some(Instruction::memory_word(CELLS)),
+            _ if token.eq_ignore_ascii_case("cell+") => Some(Instruction::memory_word(CELL_PLUS)),
+            _ if token.eq_ignore_ascii_case("base") => Some(Instruction::memory_word(BASE)),
+            _ if token.eq_ignore_ascii_case("hex") => Some(Instruction::memory_word(HEX)),
+            _ if token.eq_ignore_ascii_case("decimal") => Some(Instruction::memory_word(DECIMAL)),
+            _ if token.eq_ignore_ascii_case("binary") => Some(Instruction::memory_word(BINARY)),
+            _ => None,
+        }
+    }
+
+    /// Parses a token into a string operation.
+    /// User-defined words take precedence over the string operations.
+    fn parse_string_operation(
+        &self,
+        token: &str,
+        word_manager: &WordDefinitionManager,
+    ) -> Option<Instruction> {
+        if word_manager.is_word_defined(&WordType::UserDefined(token.to_string())) {
+            return Some(Instruction::DefinitionType(DefinitionType::Name(
+                token.to_string(),
+            )));
+        }
+
+        match token {
+            _ if token.eq_ignore_ascii_case("count") => Some(Instruction::string_word(COUNT)),
+            _ if token.eq_ignore_ascii_case("compare") => Some(Instruction::string_word(COMPARE)),
+            _ if token.eq_ignore_ascii_case("search") => Some(Instruction::string_word(SEARCH)),
+            _ if token.eq_ignore_ascii_case("/string") => {
+                Some(Instruction::string_word(SLASH_STRING))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses a token made by a string word and its text, as returned by `tokenize`.
+    /// `."` and `.(` print the text, and `S"`, `S\"` and `C"` store it in the data space.
+    /// The text keeps its case, and the escape sequences of `S\"` are replaced.
+    fn parse_string_word(&self, token: &str) -> Option<Instruction> {
+        let chars: Vec<char> = token.chars().collect();
+        let string_word = string_word_at(&chars, 0)?;
+        let word: String = chars[..string_word.length].iter().collect();
+        let text_start = string_word.text_start(&chars, 0);
+        let text: String = chars
+            .get(text_start..chars.len().checked_sub(1)?)?
+            .iter()
+            .collect();
+
+        match word.to_lowercase().as_str() {
+            ".\"" => Some(Instruction::output(OutputInstruction::dot_quote(text))),
+            ".(" => Some(Instruction::output(OutputInstruction::dot_paren(text))),
+            "s\"" => Some(Instruction::string_literal(S_QUOTE, text)),
+            "s\\\"" => Some(Instruction::string_literal(S_QUOTE, unescape(&text))),
+            "c\"" => Some(Instruction::string_literal(C_QUOTE, text)),
+            _ => None,
+        }
+    }
+
+    /// Parses a token into a defining word that takes the next token as a name.
+    ///
+    /// # Arguments
+    ///
+    /// - `token` - A string containing the token to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(DataDefinition)` if the token is VARIABLE, CONSTANT, VALUE, CREATE or TO.
+    /// - `None` otherwise.
+    fn parse_data_definition(&self, token: &str) -> Option<DataDefinition> {
+        match token {
+            _ if token.eq_ignore_ascii_case("variable") => Some(VARIABLE),
+            _ if token.eq_ignore_ascii_case("constant") => Some(CONSTANT),
+            _ if token.eq_ignore_ascii_case("value") => Some(VALUE),
+            _ if token.eq_ignore_ascii_case("create") => Some(CREATE),
+            _ if token.eq_ignore_ascii_case("to") => Some(TO),
+            _ => None,
+        }
+    }
+
+    /// Parses a token into a word that works with the dictionary.
+    ///
+    /// # Arguments
+    ///
+    /// - `token` - A string containing the token to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(DictionaryWord)` if the token is WORDS, SEE, FORGET or MARKER.
+    /// - `None` otherwise.
+    fn parse_dictionary_word(&self, token: &str) -> Option<DictionaryWord> {
+        match token {
+            _ if token.eq_ignore_ascii_case("words") => Some(WORDS),
+            _ if token.eq_ignore_ascii_case("see") => Some(SEE),
+            _ if token.eq_ignore_ascii_case("forget") => Some(FORGET),
+            _ if token.eq_ignore_ascii_case("marker") => Some(MARKER),
             _ => None,
         }
     }
@@ -388,8 +959,28 @@ impl Parser {
             _ if token.eq_ignore_ascii_case("if") => Some(Instruction::definition_type(IF)),
             _ if token.eq_ignore_ascii_case("else") => Some(Instruction::definition_type(ELSE)),
             _ if token.eq_ignore_ascii_case("then") => Some(Instruction::definition_type(THEN)),
+            _ if token.eq_ignore_ascii_case("do") => Some(Instruction::definition_type(DO)),
+            _ if token.eq_ignore_ascii_case("loop") => Some(Instruction::definition_type(LOOP)),
+            _ if token.eq_ignore_ascii_case("+loop") => {
+                Some(Instruction::definition_type(PLUS_LOOP))
+            }
+            _ if token.eq_ignore_ascii_case("i") => Some(Instruction::definition_type(LOOP_INDEX)),
+            _ if token.eq_ignore_ascii_case("j") => {
+                Some(Instruction::definition_type(OUTER_LOOP_INDEX))
+            }
+            _ if token.eq_ignore_ascii_case("leave") => Some(Instruction::definition_type(LEAVE)),
+            _ if token.eq_ignore_ascii_case("unloop") => Some(Instruction::definition_type(UNLOOP)),
+            _ if token.eq_ignore_ascii_case("exit") => Some(Instruction::definition_type(EXIT)),
+            _ if token.eq_ignore_ascii_case("begin") => Some(Instruction::definition_type(BEGIN)),
+            _ if token.eq_ignore_ascii_case("until") => Some(Instruction::definition_type(UNTIL)),
+            _ if token.eq_ignore_ascii_case("while") => Some(Instruction::definition_type(WHILE)),
+            _ if token.eq_ignore_ascii_case("repeat") => Some(Instruction::definition_type(REPEAT)),
+            _ if token.eq_ignore_ascii_case("again") => Some(Instruction::definition_type(AGAIN)),
+            _ if token.eq_ignore_ascii_case("recurse") => {
+                Some(Instruction::definition_type(RECURSE))
+            }
             _ => Some(Instruction::definition_type(DefinitionType::name(
-                token.to_string().to_lowercase(),
+                token.to_string(),
             ))),
         }
     }
@@ -416,49 +1007,528 @@ impl Parser {
     /// - `Ok(usize)` if the input string is valid and the size is extracted.
     /// - `Err(Error)` if the input string is invalid or the size is not a valid number.
     pub fn parse_stack_size(&self, input: &str) -> Result<usize, Error> {
-        let parts: Vec<&str> = input.split("=").collect();
-        if parts.len() != 2 {
-            return Err(Error::InvalidStackSize);
-        }
-
-        if let Ok(size) = parts[1].parse::<usize>() {
-            return Ok(size);
-        }
-        Err(Error::InvalidStackSize)
+        self.parse_size(input).ok_or(Error::InvalidStackSize)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::errors::Error;
-    use crate::forth::definition_type::THEN;
-    use crate::forth::intruction::Instruction;
-
-    #[test]
-    fn can_parse_simple_instructions() {
-        let parser = Parser::new();
-        let word_manager = WordDefinitionManager::new();
-        let input = String::from("1 2 +");
-        let expected_result = vec![
-            Instruction::number(1),
-            Instruction::number(2),
-            Instruction::operator("+".to_string()),
-        ];
 
-        let result = parser.parse_instructions(input, &word_manager);
+    /// Parses a data space size from a string input.
+    /// It checks if the input string is in the format "data-space-size=SIZE" and extracts the size.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - A string containing the data space size to be parsed.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    /// let parser = Parser::new();
+    /// let result = parser.parse_data_space_size("data-space-size=4096");
+    /// assert_eq!(result, Ok(4096));
+    /// ```
+    /// # Returns
+    ///
+    /// - `Ok(usize)` if the input string is valid and the size is extracted.
+    /// - `Err(Error)` if the input string is invalid or the size is not a valid number.
+    pub fn parse_data_space_size(&self, input: &str) -> Result<usize, Error> {
+        self.parse_size(input).ok_or(Error::InvalidDataSpaceSize)
+    }
 
-        assert_eq!(result, expected_result);
+    /// Parses a maximum call depth from a string input.
+    /// It checks if the input string is in the format "max-call-depth=DEPTH" and extracts the depth.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - A string containing the maximum call depth to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(usize)` if the input string is valid and the depth is extracted.
+    /// - `Err(Error)` if the input string is invalid or the depth is not a valid number.
+    pub fn parse_max_call_depth(&self, input: &str) -> Result<usize, Error> {
+        self.parse_size(input).ok_or(Error::InvalidMaxCallDepth)
     }
 
-    #[test]
-    fn can_parse_logical_instructions() {
-        let parser = Parser::new();
-        let word_manager = WordDefinitionManager::new();
-        let input = String::from("1 2 <");
-        let expected_result = vec![
-            Instruction::number(1),
-            Instruction::number(2),
+    /// Parses an error format from a string input.
+    /// It checks if the input string is in the format "error-format=FORMAT",
+    /// where FORMAT is `terse` or `rich`.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - A string containing the error format to be parsed.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::errors::ErrorFormat;
+    /// let parser = Parser::new();
+    /// let result = parser.parse_error_format("error-format=rich");
+    /// assert_eq!(result, Ok(ErrorFormat::Rich));
+    /// ```
+    pub fn parse_error_format(&self, input: &str) -> Result<ErrorFormat, Error> {
+        match input.split_once("=") {
+            Some((_, format)) if format.eq_ignore_ascii_case("terse") => Ok(ErrorFormat::Terse),
+            Some((_, format)) if format.eq_ignore_ascii_case("rich") => Ok(ErrorFormat::Rich),
+            _ => Err(Error::InvalidErrorFormat),
+        }
+    }
+
+    /// Parses a cell size from a string input.
+    /// It checks if the input string is in the format "cell-size=BITS",
+    /// where BITS is `16`, `32` or `64` (optionally written as `i16`, `i32` or `i64`).
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - A string containing the cell size to be parsed.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::cell::CellSize;
+    /// let parser = Parser::new();
+    /// let result = parser.parse_cell_size("cell-size=32");
+    /// assert_eq!(result, Ok(CellSize::I32));
+    /// ```
+    pub fn parse_cell_size(&self, input: &str) -> Result<CellSize, Error> {
+        let bits = input
+            .split_once("=")
+            .map(|(_, size)| size.trim_start_matches(['i', 'I']));
+        match bits {
+            Some("16") => Ok(CellSize::I16),
+            Some("32") => Ok(CellSize::I32),
+            Some("64") => Ok(CellSize::I64),
+            _ => Err(Error::InvalidCellSize),
+        }
+    }
+
+    /// Parses an arithmetic policy from a string input.
+    /// It checks if the input string is in the format "arithmetic=POLICY",
+    /// where POLICY is `wrapping`, `checked` or `saturating`.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - A string containing the arithmetic policy to be parsed.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::calculator::arithmetic_policy::ArithmeticPolicy;
+    /// let parser = Parser::new();
+    /// let result = parser.parse_arithmetic_policy("arithmetic=checked");
+    /// assert_eq!(result, Ok(ArithmeticPolicy::Checked));
+    /// ```
+    pub fn parse_arithmetic_policy(&self, input: &str) -> Result<ArithmeticPolicy, Error> {
+        match input.split_once("=") {
+            Some((_, policy)) if policy.eq_ignore_ascii_case("wrapping") => {
+                Ok(ArithmeticPolicy::Wrapping)
+            }
+            Some((_, policy)) if policy.eq_ignore_ascii_case("checked") => {
+                Ok(ArithmeticPolicy::Checked)
+            }
+            Some((_, policy)) if policy.eq_ignore_ascii_case("saturating") => {
+                Ok(ArithmeticPolicy::Saturating)
+            }
+            _ => Err(Error::InvalidArithmeticPolicy),
+        }
+    }
+
+    /// Parses a division mode from a string input.
+    /// It checks if the input string is in the format "division=MODE",
+    /// where MODE is `symmetric` or `floored`.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - A string containing the division mode to be parsed.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::calculator::division_mode::DivisionMode;
+    /// let parser = Parser::new();
+    /// let result = parser.parse_division_mode("division=floored");
+    /// assert_eq!(result, Ok(DivisionMode::Floored));
+    /// ```
+    pub fn parse_division_mode(&self, input: &str) -> Result<DivisionMode, Error> {
+        match input.split_once("=") {
+            Some((_, mode)) if mode.eq_ignore_ascii_case("symmetric") => {
+                Ok(DivisionMode::Symmetric)
+            }
+            Some((_, mode)) if mode.eq_ignore_ascii_case("floored") => Ok(DivisionMode::Floored),
+            _ => Err(Error::InvalidDivisionMode),
+        }
+    }
+
+    /// Parses a boolean mode from a string input.
+    /// It checks if the input string is in the format "boolean=MODE",
+    /// where MODE is `bitwise` or `logical`.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - A string containing the boolean mode to be parsed.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::forth::boolean_operations::BooleanMode;
+    /// let parser = Parser::new();
+    /// let result = parser.parse_boolean_mode("boolean=logical");
+    /// assert_eq!(result, Ok(BooleanMode::Logical));
+    /// ```
+    pub fn parse_boolean_mode(&self, input: &str) -> Result<BooleanMode, Error> {
+        match input.split_once("=") {
+            Some((_, mode)) if mode.eq_ignore_ascii_case("bitwise") => Ok(BooleanMode::Bitwise),
+            Some((_, mode)) if mode.eq_ignore_ascii_case("logical") => Ok(BooleanMode::Logical),
+            _ => Err(Error::InvalidBooleanMode),
+        }
+    }
+
+    /// Parses an output mode from a string input.
+    /// It checks if the input string is in the format "output=MODE",
+    /// where MODE is `legacy` or `standard`.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - A string containing the output mode to be parsed.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::forth::output_instructions::OutputMode;
+    /// let parser = Parser::new();
+    /// let result = parser.parse_output_mode("output=standard");
+    /// assert_eq!(result, Ok(OutputMode::Standard));
+    /// ```
+    pub fn parse_output_mode(&self, input: &str) -> Result<OutputMode, Error> {
+        match input.split_once("=") {
+            Some((_, mode)) if mode.eq_ignore_ascii_case("legacy") => Ok(OutputMode::Legacy),
+            Some((_, mode)) if mode.eq_ignore_ascii_case("standard") => Ok(OutputMode::Standard),
+            _ => Err(Error::InvalidOutputMode),
+        }
+    }
+
+    /// Parses the case sensitivity of the names of user-defined words from a string input.
+    /// It checks if the input string is in the format "case=MODE",
+    /// where MODE is `insensitive` or `sensitive`.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - A string containing the case sensitivity to be parsed.
+    ///
+    /// # Examples
+    /// ```
+    ///# use rust_forth::forth::parser::Parser;
+    ///# use rust_forth::forth::word::CaseSensitivity;
+    /// let parser = Parser::new();
+    /// let result = parser.parse_case_sensitivity("case=sensitive");
+    /// assert_eq!(result, Ok(CaseSensitivity::Sensitive));
+    /// ```
+    pub fn parse_case_sensitivity(&self, input: &str) -> Result<CaseSensitivity, Error> {
+        match input.split_once("=") {
+            Some((_, mode)) if mode.eq_ignore_ascii_case("insensitive") => {
+                Ok(CaseSensitivity::Insensitive)
+            }
+            Some((_, mode)) if mode.eq_ignore_ascii_case("sensitive") => {
+                Ok(CaseSensitivity::Sensitive)
+            }
+            _ => Err(Error::InvalidCaseSensitivity),
+        }
+    }
+
+    /// Extracts the size from an input in the format "KEY=SIZE".
+    fn parse_size(&self, input: &str) -> Option<usize> {
+        let parts: Vec<&str> = input.split("=").collect();
+        if parts.len() != 2 {
+            return None;
+        }
+        parts[1].parse::<usize>().ok()
+    }
+}
+
+/// Kinds of comments: from `\` to the end of the line, or from `(` to the next `)`.
+#[derive(Debug, PartialEq)]
+enum Comment {
+    Line,
+    Parenthesised,
+    Unclosed,
+}
+
+/// Finds the comment that starts at `index`, if any, and the index right after it.
+/// Comments start with a `\` or `(` word, so they must be surrounded by whitespace.
+/// A parenthesised comment may span several lines; if it is not closed,
+/// it takes the rest of the input.
+fn find_comment(chars: &[char], index: usize) -> Option<(Comment, usize)> {
+    let is_word_start = index == 0 || chars[index - 1].is_whitespace();
+    let is_word_end = chars.get(index + 1).is_none_or(|c| c.is_whitespace());
+    if !is_word_start || !is_word_end {
+        return None;
+    }
+
+    let position = |target: char| {
+        chars[index..]
+            .iter()
+            .position(|&c| c == target)
+            .map(|offset| index + offset)
+    };
+    match chars[index] {
+        '\\' => Some((Comment::Line, position('\n').unwrap_or(chars.len()))),
+        '(' => match position(')') {
+            Some(end) => Some((Comment::Parenthesised, end + 1)),
+            None => Some((Comment::Unclosed, chars.len())),
+        },
+        _ => None,
+    }
+}
+
+/// Words that parse the text that follows them, with the delimiter of the text
+/// and whether it may contain escape sequences.
+const STRING_WORDS: [(&str, char, bool); 5] = [
+    (".\"", '"', false),
+    (".(", ')', false),
+    ("s\"", '"', false),
+    ("s\\\"", '"', true),
+    ("c\"", '"', false),
+];
+
+/// Word that parses the text that follows it, up to a delimiter.
+#[derive(Debug, PartialEq)]
+struct StringWord {
+    length: usize,
+    delimiter: char,
+    escaped: bool,
+}
+
+impl StringWord {
+    /// Gets the index where the text of the word that starts at `index` begins:
+    /// right after the word, skipping the whitespace that separates them, if any.
+    fn text_start(&self, chars: &[char], index: usize) -> usize {
+        let end = index + self.length;
+        end + usize::from(chars.get(end).is_some_and(|c| c.is_whitespace()))
+    }
+}
+
+/// Finds the string word that starts at `index`, if any.
+/// The known string words (`."`, `.(`, `S"`, `S\"`, `C"`) may be followed by their text
+/// without a space, as in `."Hello"`; any other word ending with a quote takes the text
+/// that follows it after a space, up to the next quote.
+fn string_word_at(chars: &[char], index: usize) -> Option<StringWord> {
+    if index > 0 && !chars[index - 1].is_whitespace() {
+        return None;
+    }
+    let rest = &chars[index..];
+    for (word, delimiter, escaped) in STRING_WORDS {
+        let length = word.chars().count();
+        let is_prefix = rest.len() >= length
+            && rest
+                .iter()
+                .zip(word.chars())
+                .all(|(c, expected)| c.eq_ignore_ascii_case(&expected));
+        if is_prefix {
+            return Some(StringWord {
+                length,
+                delimiter,
+                escaped,
+            });
+        }
+    }
+
+    let length = rest.iter().position(|c| c.is_whitespace())?;
+    match &rest[..length] {
+        [word @ .., '"'] if !word.is_empty() && !word.contains(&'"') => Some(StringWord {
+            length,
+            delimiter: '"',
+            escaped: false,
+        }),
+        _ => None,
+    }
+}
+
+/// Finds the text parsed by the string word that starts at `index`, if any,
+/// and the index right after it, together with whether the text was closed.
+/// Escaped delimiters do not close the text of `S\"`.
+/// If the text is not closed, it takes the rest of the input.
+fn find_string(chars: &[char], index: usize) -> Option<(usize, bool)> {
+    let string_word = string_word_at(chars, index)?;
+
+    let mut i = string_word.text_start(chars, index);
+    while i < chars.len() {
+        if string_word.escaped && chars[i] == '\\' {
+            i += 2;
+        } else if chars[i] == string_word.delimiter {
+            return Some((i + 1, true));
+        } else {
+            i += 1;
+        }
+    }
+    Some((chars.len(), false))
+}
+
+/// Replaces the escape sequences of the text of `S\"`: `\n`, `\t`, `\"`, `\\`,
+/// `\xHH` (a byte given by two hexadecimal digits) and the rest of the standard ones.
+/// Unknown sequences are replaced by the escaped character.
+/// Returns the bytes of the text, since a `\xHH` byte does not need to be valid UTF-8.
+fn unescape(text: &str) -> Vec<u8> {
+    let mut result = Vec::new();
+    let mut chars = text.chars().peekable();
+    let push = |result: &mut Vec<u8>, character: char| {
+        result.extend_from_slice(character.encode_utf8(&mut [0; 4]).as_bytes());
+    };
+
+    while let Some(character) = chars.next() {
+        if character != '\\' {
+            push(&mut result, character);
+            continue;
+        }
+        match chars.next() {
+            Some('a') => result.push(0x07),
+            Some('b') => result.push(0x08),
+            Some('e') => result.push(0x1b),
+            Some('f') => result.push(0x0c),
+            Some('l') | Some('n') => result.push(b'\n'),
+            Some('m') => result.extend_from_slice(b"\r\n"),
+            Some('q') => result.push(b'"'),
+            Some('r') => result.push(b'\r'),
+            Some('t') => result.push(b'\t'),
+            Some('v') => result.push(0x0b),
+            Some('z') => result.push(0),
+            Some('x') => {
+                let mut code = 0;
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(HEX_BASE)) {
+                        Some(digit) => {
+                            code = code * HEX_BASE as u8 + digit as u8;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                result.push(code);
+            }
+            Some(escaped) => push(&mut result, escaped),
+            None => result.push(b'\\'),
+        }
+    }
+    result
+}
+
+/// Removes the comments from the input, keeping its line breaks.
+/// Returns the remaining code, and whether a parenthesised comment or the text of a string word
+/// is left open at the end, which means that it continues on the next line.
+///
+/// # Examples
+/// ```
+///# use rust_forth::forth::parser::strip_comments;
+/// let (code, open) = strip_comments(": double ( n -- 2n ) dup + ; \\ doubles");
+/// assert_eq!(code.split_whitespace().collect::<Vec<_>>(), [":", "double", "dup", "+", ";"]);
+/// assert!(!open);
+///
+/// let (_, open) = strip_comments("1 ( the comment goes on");
+/// assert!(open);
+/// ```
+pub fn strip_comments(input: &str) -> (String, bool) {
+    let chars: Vec<char> = input.chars().collect();
+    let mut code = String::new();
+    let mut open = false;
+
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some((comment, end)) = find_comment(&chars, i) {
+            open = comment == Comment::Unclosed;
+            code.extend(chars[i..end].iter().filter(|&&c| c == '\n'));
+            i = end;
+        } else if let Some((end, closed)) = find_string(&chars, i) {
+            open = !closed;
+            code.extend(&chars[i..end]);
+            i = end;
+        } else {
+            code.push(chars[i]);
+            i += 1;
+        }
+    }
+    (code, open)
+}
+
+/// Gets the text of a parenthesised comment token, without the parentheses.
+fn parenthesised_comment(token: &str) -> Option<String> {
+    let text = token.strip_prefix('(')?.strip_suffix(')')?;
+    if !text.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Checks if an instruction interpreted outside a definition may change `BASE` in a way
+/// the parser can not follow: `BASE` itself, a word or the start of a control structure.
+fn may_change_base(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::MemoryWord(MemoryOperation::Base) => true,
+        Instruction::DefinitionType(DefinitionType::Name(_)) => true,
+        Instruction::DefinitionType(definition_type) => definition_type.opens_block(),
+        _ => false,
+    }
+}
+
+/// Checks if the last instruction is the name of the definition being parsed,
+/// where a parenthesised comment is its stack effect.
+fn follows_word_name(instructions: &[Instruction]) -> bool {
+    matches!(
+        instructions,
+        [
+            ..,
+            Instruction::StartDefinition,
+            Instruction::DefinitionType(DefinitionType::Name(_))
+        ]
+    )
+}
+
+/// Builds the error for a string word whose text is not closed, located at the word.
+fn unterminated_string_error(span: Span) -> Error {
+    let word = span.word.to_string();
+    Error::from(ForthError::UnterminatedString(span.column)).locate(Location::new(&span, word))
+}
+
+/// Builds the error for a word that takes the name that follows it when the input ends
+/// right after it, located at the word.
+fn missing_name_error(span: Span) -> Error {
+    let word = span.word.to_string();
+    Error::from(ForthError::MissingName).locate(Location::new(&span, word))
+}
+
+/// Builds the error for a token that cannot be resolved, located at the token.
+fn unknown_token_error(span: Span) -> Error {
+    let unknown = UnknownToken::new(span.word.to_string(), span.column);
+    let word = span.word.to_string();
+    Error::from(ForthError::UnknownWord(Some(unknown))).locate(Location::new(&span, word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::Error;
+    use crate::forth::definition_type::THEN;
+    use crate::forth::intruction::Instruction;
+
+    #[test]
+    fn can_parse_simple_instructions() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("1 2 +");
+        let expected_result = vec![
+            Instruction::number(1),
+            Instruction::number(2),
+            Instruction::operator("+".to_string()),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_logical_instructions() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("1 2 <");
+        let expected_result = vec![
+            Instruction::number(1),
+            Instruction::number(2),
             Instruction::logical_operation(LESS_THAN),
         ];
 
@@ -506,14 +1576,38 @@ mod tests {
         assert_eq!(result, expected_result);
     }
 
+    #[test]
+    fn can_parse_the_extended_stack_words() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("nip TUCK pick roll -rot ?dup 2dup 2drop 2swap 2over depth");
+        let expected_result = vec![
+            Instruction::stack_word(NIP),
+            Instruction::stack_word(TUCK),
+            Instruction::stack_word(PICK),
+            Instruction::stack_word(ROLL),
+            Instruction::stack_word(MINUS_ROT),
+            Instruction::stack_word(QUESTION_DUP),
+            Instruction::stack_word(TWO_DUP),
+            Instruction::stack_word(TWO_DROP),
+            Instruction::stack_word(TWO_SWAP),
+            Instruction::stack_word(TWO_OVER),
+            Instruction::stack_word(DEPTH),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
     #[test]
     fn can_parse_defined_words() {
         let parser = Parser::new();
         let word_manager = WordDefinitionManager::new();
         let input = String::from("AWORD *WORD*");
         let expected_result = vec![
-            Instruction::definition_type(DefinitionType::name("aword".to_string())),
-            Instruction::definition_type(DefinitionType::name("*word*".to_string())),
+            Instruction::definition_type(DefinitionType::name("AWORD".to_string())),
+            Instruction::definition_type(DefinitionType::name("*WORD*".to_string())),
         ];
 
         let result = parser.parse_instructions(input, &word_manager);
@@ -669,17 +1763,772 @@ mod tests {
 
     #[test]
     fn test_case_insensitive_words() {
+        let parser = Parser::new();
+        let mut word_manager = WordDefinitionManager::new();
+        let _ = word_manager.define_new_word(
+            WordType::UserDefined("aword".to_string()),
+            vec![Instruction::number(1), Instruction::end_definition()],
+        );
+        let input = String::from("aWord Aword DUP");
+        let expected_result = vec![
+            Instruction::definition_type(DefinitionType::name("aWord".to_string())),
+            Instruction::definition_type(DefinitionType::name("Aword".to_string())),
+            Instruction::stack_word(DUP),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_definition_with_counted_loops() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from(": table 3 0 DO 3 0 do I j * 2 +LOOP leave unloop exit Loop ;");
+        let expected_result = vec![
+            Instruction::start_definition(),
+            Instruction::definition_type(DefinitionType::name("table".to_string())),
+            Instruction::number(3),
+            Instruction::number(0),
+            Instruction::definition_type(DO),
+            Instruction::number(3),
+            Instruction::number(0),
+            Instruction::definition_type(DO),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::definition_type(OUTER_LOOP_INDEX),
+            Instruction::operator("*".to_string()),
+            Instruction::number(2),
+            Instruction::definition_type(PLUS_LOOP),
+            Instruction::definition_type(LEAVE),
+            Instruction::definition_type(UNLOOP),
+            Instruction::definition_type(EXIT),
+            Instruction::definition_type(LOOP),
+            Instruction::end_definition(),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_definition_with_indefinite_loops() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from(": f BEGIN dup WHILE 1 - Repeat begin again begin until ;");
+        let expected_result = vec![
+            Instruction::start_definition(),
+            Instruction::definition_type(DefinitionType::name("f".to_string())),
+            Instruction::definition_type(BEGIN),
+            Instruction::stack_word(DUP),
+            Instruction::definition_type(WHILE),
+            Instruction::number(1),
+            Instruction::operator("-".to_string()),
+            Instruction::definition_type(REPEAT),
+            Instruction::definition_type(BEGIN),
+            Instruction::definition_type(AGAIN),
+            Instruction::definition_type(BEGIN),
+            Instruction::definition_type(UNTIL),
+            Instruction::end_definition(),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_return_stack_operations() {
         let parser = Parser::new();
         let word_manager = WordDefinitionManager::new();
-        let input = String::from("aWord Aword aword");
+        let input = String::from("1 >r R@ r> 2>R 2r>");
         let expected_result = vec![
-            Instruction::definition_type(DefinitionType::name("aword".to_string())),
-            Instruction::definition_type(DefinitionType::name("aword".to_string())),
-            Instruction::definition_type(DefinitionType::name("aword".to_string())),
+            Instruction::number(1),
+            Instruction::stack_word(TO_R),
+            Instruction::stack_word(R_FETCH),
+            Instruction::stack_word(R_FROM),
+            Instruction::stack_word(TWO_TO_R),
+            Instruction::stack_word(TWO_R_FROM),
         ];
 
         let result = parser.parse_instructions(input, &word_manager);
 
         assert_eq!(result, expected_result);
     }
+
+    #[test]
+    fn can_parse_data_space_size() {
+        let parser = Parser::new();
+        let input = "data-space-size=2048";
+        let expected_result: usize = 2048;
+
+        let result = parser.parse_data_space_size(input);
+
+        assert_eq!(result, Ok(expected_result));
+    }
+
+    #[test]
+    fn try_parse_invalid_data_space_size_shoud_throw_error() {
+        let parser = Parser::new();
+        let input = "data-space-size=big";
+
+        let result = parser.parse_data_space_size(input);
+
+        assert_eq!(result, Err(Error::InvalidDataSpaceSize));
+    }
+
+    #[test]
+    fn can_parse_memory_operations() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("1 2 ! 3 @ +! c@ C! , here ALLOT cells cell+");
+        let expected_result = vec![
+            Instruction::number(1),
+            Instruction::number(2),
+            Instruction::memory_word(STORE),
+            Instruction::number(3),
+            Instruction::memory_word(FETCH),
+            Instruction::memory_word(PLUS_STORE),
+            Instruction::memory_word(CHAR_FETCH),
+            Instruction::memory_word(CHAR_STORE),
+            Instruction::memory_word(COMMA),
+            Instruction::memory_word(HERE),
+            Instruction::memory_word(ALLOT),
+            Instruction::memory_word(CELLS),
+            Instruction::memory_word(CELL_PLUS),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_data_definitions_with_their_names() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("variable x 5 constant five 1 value v create buf 2 to v");
+        let expected_result = vec![
+            Instruction::data_definition(VARIABLE, "x".to_string()),
+            Instruction::number(5),
+            Instruction::data_definition(CONSTANT, "five".to_string()),
+            Instruction::number(1),
+            Instruction::data_definition(VALUE, "v".to_string()),
+            Instruction::data_definition(CREATE, "buf".to_string()),
+            Instruction::number(2),
+            Instruction::data_definition(TO, "v".to_string()),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn data_definitions_without_a_name_are_reported_at_their_word() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("1 value v\n2 to");
+        let location = Location::new(&Span::new(2, 3, "to"), "to");
+        let expected_error = Error::from(ForthError::MissingName).locate(location);
+
+        let result = parser.parse_with_spans(input, &word_manager, 1);
+
+        assert_eq!(result, Err(expected_error));
+    }
+
+    #[test]
+    fn can_parse_max_call_depth() {
+        let parser = Parser::new();
+
+        assert_eq!(parser.parse_max_call_depth("max-call-depth=50"), Ok(50));
+        assert_eq!(
+            parser.parse_max_call_depth("max-call-depth="),
+            Err(Error::InvalidMaxCallDepth)
+        );
+    }
+
+    #[test]
+    fn can_parse_cell_size() {
+        let parser = Parser::new();
+
+        assert_eq!(parser.parse_cell_size("cell-size=16"), Ok(CellSize::I16));
+        assert_eq!(parser.parse_cell_size("cell-size=i64"), Ok(CellSize::I64));
+        assert_eq!(
+            parser.parse_cell_size("cell-size=8"),
+            Err(Error::InvalidCellSize)
+        );
+    }
+
+    #[test]
+    fn can_parse_arithmetic_policy() {
+        let parser = Parser::new();
+
+        assert_eq!(
+            parser.parse_arithmetic_policy("arithmetic=saturating"),
+            Ok(ArithmeticPolicy::Saturating)
+        );
+        assert_eq!(
+            parser.parse_arithmetic_policy("arithmetic=Wrapping"),
+            Ok(ArithmeticPolicy::Wrapping)
+        );
+        assert_eq!(
+            parser.parse_arithmetic_policy("arithmetic=clamped"),
+            Err(Error::InvalidArithmeticPolicy)
+        );
+    }
+
+    #[test]
+    fn can_parse_division_mode() {
+        let parser = Parser::new();
+
+        assert_eq!(
+            parser.parse_division_mode("division=symmetric"),
+            Ok(DivisionMode::Symmetric)
+        );
+        assert_eq!(
+            parser.parse_division_mode("division=rounded"),
+            Err(Error::InvalidDivisionMode)
+        );
+    }
+
+    #[test]
+    fn can_parse_boolean_mode() {
+        let parser = Parser::new();
+
+        assert_eq!(
+            parser.parse_boolean_mode("boolean=bitwise"),
+            Ok(BooleanMode::Bitwise)
+        );
+        assert_eq!(
+            parser.parse_boolean_mode("boolean=fuzzy"),
+            Err(Error::InvalidBooleanMode)
+        );
+    }
+
+    #[test]
+    fn can_parse_output_mode() {
+        let parser = Parser::new();
+
+        assert_eq!(
+            parser.parse_output_mode("output=legacy"),
+            Ok(OutputMode::Legacy)
+        );
+        assert_eq!(
+            parser.parse_output_mode("output=pretty"),
+            Err(Error::InvalidOutputMode)
+        );
+    }
+
+    #[test]
+    fn can_parse_case_sensitivity() {
+        let parser = Parser::new();
+
+        assert_eq!(
+            parser.parse_case_sensitivity("case=insensitive"),
+            Ok(CaseSensitivity::Insensitive)
+        );
+        assert_eq!(
+            parser.parse_case_sensitivity("case=upper"),
+            Err(Error::InvalidCaseSensitivity)
+        );
+    }
+
+    #[test]
+    fn can_parse_dictionary_words() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("WORDS see Square 1 Marker -work- forget Square");
+        let expected_result = vec![
+            Instruction::dictionary_word(WORDS, ""),
+            Instruction::dictionary_word(SEE, "Square"),
+            Instruction::number(1),
+            Instruction::dictionary_word(MARKER, "-work-"),
+            Instruction::dictionary_word(FORGET, "Square"),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn dictionary_words_without_a_name_are_reported_at_their_word() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+
+        for word in ["marker", "forget", "see"] {
+            let input = format!("words {}", word);
+            let location = Location::new(&Span::new(1, 7, word), word);
+            let expected_error = Error::from(ForthError::MissingName).locate(location);
+
+            let result = parser.parse_with_spans(input, &word_manager, 1);
+
+            assert_eq!(result, Err(expected_error));
+        }
+    }
+
+    #[test]
+    fn line_comments_are_skipped_and_parenthesised_ones_kept_whole() {
+        let parser = Parser::new();
+        let input = "1 \\ 2 ( 3 )\n( 4\n5 ) 6 (7) \\";
+        let expected_result = vec![
+            Span::new(1, 1, "1"),
+            Span::new(2, 1, "( 4\n5 )"),
+            Span::new(3, 5, "6"),
+            Span::new(3, 7, "(7)"),
+        ];
+
+        let result = parser.tokenize(input, 1);
+
+        assert_eq!(result, Ok(expected_result));
+    }
+
+    #[test]
+    fn comments_are_not_parsed_as_words() {
+        let parser = Parser::new().with_strict_mode(true);
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("1 ( undefined words ) 2 \\ more undefined words");
+        let expected_result = vec![Instruction::number(1), Instruction::number(2)];
+
+        let result = parser.try_parse_instructions(input, &word_manager);
+
+        assert_eq!(result, Ok(expected_result));
+    }
+
+    #[test]
+    fn the_comment_after_the_name_is_the_stack_effect() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from(": double ( n --\n  2n ) ( not this one ) dup + ;");
+        let expected_result = vec![
+            Instruction::start_definition(),
+            Instruction::definition_type(DefinitionType::name("double")),
+            Instruction::stack_effect("n -- 2n"),
+            Instruction::stack_word(DUP),
+            Instruction::operator("+"),
+            Instruction::end_definition(),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_strip_comments() {
+        let (code, open) = strip_comments("1 ( a ) 2 \\ 3\n4 ( 5");
+
+        assert_eq!(code, "1  2 \n4 ");
+        assert!(open);
+    }
+
+    #[test]
+    fn can_parse_spacing_words() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("space 3 SPACES bl emit");
+        let expected_result = vec![
+            Instruction::output(SPACE),
+            Instruction::number(3),
+            Instruction::output(SPACES),
+            Instruction::number(32),
+            Instruction::output(EMIT),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_the_bitwise_word_set() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("xor INVERT lshift rshift arshift");
+        let expected_result = vec![
+            Instruction::boolean_operation(XOR),
+            Instruction::boolean_operation(INVERT),
+            Instruction::boolean_operation(LSHIFT),
+            Instruction::boolean_operation(RSHIFT),
+            Instruction::boolean_operation(ARSHIFT),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_the_comparison_word_set() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("<> <= >= 0= 0< 0> 0<> U< u> Within");
+        let expected_result = vec![
+            Instruction::logical_operation(NOT_EQUAL),
+            Instruction::logical_operation(LESS_OR_EQUAL),
+            Instruction::logical_operation(GREATER_OR_EQUAL),
+            Instruction::logical_operation(ZERO_EQUAL),
+            Instruction::logical_operation(ZERO_LESS),
+            Instruction::logical_operation(ZERO_GREATER),
+            Instruction::logical_operation(ZERO_NOT_EQUAL),
+            Instruction::logical_operation(U_LESS_THAN),
+            Instruction::logical_operation(U_GREATER_THAN),
+            Instruction::logical_operation(WITHIN),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_the_arithmetic_word_set() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("7 2 MOD 1+ negate */mod 2/");
+        let expected_result = vec![
+            Instruction::number(7),
+            Instruction::number(2),
+            Instruction::operator("mod"),
+            Instruction::operator("1+"),
+            Instruction::operator("negate"),
+            Instruction::operator("*/mod"),
+            Instruction::operator("2/"),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn number_literals_are_limited_by_the_cell_size() {
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("40000");
+
+        let narrow_result = Parser::new().parse_instructions(input.clone(), &word_manager);
+        let wide_result = Parser::new()
+            .with_cell_size(CellSize::I32)
+            .parse_instructions(input, &word_manager);
+
+        assert_eq!(narrow_result, vec![]);
+        assert_eq!(wide_result, vec![Instruction::number(40000)]);
+    }
+
+    #[test]
+    fn can_parse_literals_in_other_bases() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("$FF #10 %1010 'A' $-10 hex ff 10 decimal 10 binary 11");
+        let expected_result = vec![
+            Instruction::number(255),
+            Instruction::number(10),
+            Instruction::number(10),
+            Instruction::number(65),
+            Instruction::number(-16),
+            Instruction::memory_word(HEX),
+            Instruction::number(255),
+            Instruction::number(16),
+            Instruction::memory_word(DECIMAL),
+            Instruction::number(10),
+            Instruction::memory_word(BINARY),
+            Instruction::number(3),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn words_take_precedence_over_literals_in_other_bases() {
+        let parser = Parser::new().with_base(36);
+        let mut word_manager = WordDefinitionManager::new();
+        let _ = word_manager.define_new_word(
+            WordType::UserDefined("cafe".to_string()),
+            vec![Instruction::number(1), Instruction::end_definition()],
+        );
+        let input = String::from("dup z : foo hex ff ; cafe");
+        let expected_result = vec![
+            Instruction::stack_word(DUP),
+            Instruction::number(35),
+            Instruction::start_definition(),
+            Instruction::definition_type(DefinitionType::name("foo")),
+            Instruction::memory_word(HEX),
+            Instruction::number(15 * 36 + 15),
+            Instruction::end_definition(),
+            Instruction::definition_type(DefinitionType::name("cafe")),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn literals_after_changes_of_the_base_are_kept_as_tokens() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("10 16 base ! 10 $10 : foo 10 ; hex 10");
+        let expected_result = vec![
+            Instruction::number(10),
+            Instruction::number(16),
+            Instruction::memory_word(BASE),
+            Instruction::memory_word(STORE),
+            Instruction::literal("10"),
+            Instruction::number(16),
+            Instruction::start_definition(),
+            Instruction::definition_type(DefinitionType::name("foo")),
+            Instruction::literal("10"),
+            Instruction::end_definition(),
+            Instruction::memory_word(HEX),
+            Instruction::literal("10"),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_pictured_output_words() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("<# # #S 46 hold sign #> type u. .r U.R");
+        let expected_result = vec![
+            Instruction::output(LESS_NUMBER_SIGN),
+            Instruction::output(NUMBER_SIGN),
+            Instruction::output(NUMBER_SIGN_S),
+            Instruction::number(46),
+            Instruction::output(HOLD),
+            Instruction::output(SIGN),
+            Instruction::output(NUMBER_SIGN_GREATER),
+            Instruction::output(TYPE),
+            Instruction::output(U_DOT),
+            Instruction::output(DOT_R),
+            Instruction::output(U_DOT_R),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_memory_display_words() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("here ? here 2 DUMP");
+        let expected_result = vec![
+            Instruction::memory_word(HERE),
+            Instruction::output(QUESTION),
+            Instruction::memory_word(HERE),
+            Instruction::number(2),
+            Instruction::output(DUMP),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn user_words_take_precedence_over_the_memory_display_words() {
+        let parser = Parser::new();
+        let mut word_manager = WordDefinitionManager::new();
+        let _ = word_manager.define_new_word(
+            WordType::UserDefined("?".to_string()),
+            vec![Instruction::number(1), Instruction::end_definition()],
+        );
+        let _ = word_manager.define_new_word(
+            WordType::UserDefined("dump".to_string()),
+            vec![Instruction::number(1), Instruction::end_definition()],
+        );
+        let input = String::from("? DUMP");
+        let expected_result = vec![
+            Instruction::definition_type(DefinitionType::name("?".to_string())),
+            Instruction::definition_type(DefinitionType::name("DUMP".to_string())),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_string_words() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from(r#"S" a b" c"  c" .( d) count compare search /string"#);
+        let expected_result = vec![
+            Instruction::string_literal(S_QUOTE, "a b"),
+            Instruction::string_literal(C_QUOTE, " c"),
+            Instruction::output(OutputInstruction::dot_paren("d")),
+            Instruction::string_word(COUNT),
+            Instruction::string_word(COMPARE),
+            Instruction::string_word(SEARCH),
+            Instruction::string_word(SLASH_STRING),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn string_words_keep_their_text_in_a_single_token() {
+        let parser = Parser::new();
+        let input = "1 s\" ( : ; \\ )\" 2 .\"Hi\" S\\\"a\\\"b\" .( ñandú\n2 )";
+        let expected_result = vec![
+            Span::new(1, 1, "1"),
+            Span::new(1, 3, r#"s" ( : ; \ )""#),
+            Span::new(1, 17, "2"),
+            Span::new(1, 19, r#"."Hi""#),
+            Span::new(1, 25, r#"S\"a\"b""#),
+            Span::new(1, 34, ".( ñandú\n2 )"),
+        ];
+
+        let result = parser.tokenize(input, 1);
+
+        assert_eq!(result, Ok(expected_result));
+    }
+
+    #[test]
+    fn unterminated_strings_are_reported_at_their_word() {
+        let parser = Parser::new();
+        let input = "1 .\" Hi\" .\n  s\" unclosed";
+        let location = Location::new(&Span::new(2, 3, "s\""), "s\"");
+        let expected_error = Error::from(ForthError::UnterminatedString(3)).locate(location);
+
+        let result = parser.tokenize(input, 1);
+
+        assert_eq!(result, Err(expected_error));
+    }
+
+    #[test]
+    fn string_words_keep_the_case_of_their_text() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from("S\" Hello\" .\"World\" DUP 'A' C\"Ñu\"");
+        let expected_result = vec![
+            Instruction::string_literal(S_QUOTE, "Hello"),
+            Instruction::output(OutputInstruction::dot_quote("World")),
+            Instruction::stack_word(DUP),
+            Instruction::number('A' as Cell),
+            Instruction::string_literal(C_QUOTE, "Ñu"),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_replace_escape_sequences() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from(r#"s\" a\tb\n\"c\"\\\x41\x7e\q" s\" \xE9\xff0" s" a\tb""#);
+        let expected_result = vec![
+            Instruction::string_literal(S_QUOTE, "a\tb\n\"c\"\\A~\""),
+            Instruction::string_literal(S_QUOTE, [b'\xe9', b'\xff', b'0']),
+            Instruction::string_literal(S_QUOTE, r"a\tb"),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_parse_recurse() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from(": fact dup 1 > if dup 1 - recurse * then ;");
+        let expected_result = vec![
+            Instruction::start_definition(),
+            Instruction::definition_type(DefinitionType::name("fact")),
+            Instruction::stack_word(DUP),
+            Instruction::number(1),
+            Instruction::logical_operation(GREATER_THAN),
+            Instruction::definition_type(IF),
+            Instruction::stack_word(DUP),
+            Instruction::number(1),
+            Instruction::operator("-".to_string()),
+            Instruction::definition_type(RECURSE),
+            Instruction::operator("*".to_string()),
+            Instruction::definition_type(THEN),
+            Instruction::end_definition(),
+        ];
+
+        let result = parser.parse_instructions(input, &word_manager);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn strict_mode_reports_undefined_words_inside_definitions() {
+        let parser = Parser::new().with_strict_mode(true);
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from(": double dup + ; : quad double duble ;");
+        let expected_error = ForthError::UnknownWord(Some(UnknownToken::new("duble", 32)));
+
+        let result = parser.try_parse_instructions(input, &word_manager);
+
+        assert_eq!(result, Err(expected_error.into()));
+    }
+
+    #[test]
+    fn strict_mode_allows_self_reference_and_defined_words() {
+        let parser = Parser::new().with_strict_mode(true);
+        let mut word_manager = WordDefinitionManager::new();
+        let _ = word_manager.define_new_word(
+            WordType::UserDefined("double".to_string()),
+            vec![Instruction::number(2), Instruction::end_definition()],
+        );
+        let input = String::from(": fact dup if double fact then ; undefined");
+
+        let result = parser.try_parse_instructions(input, &word_manager);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn strict_mode_reports_out_of_range_numbers() {
+        let parser = Parser::new().with_strict_mode(true);
+        let word_manager = WordDefinitionManager::new();
+        let expected_error = ForthError::UnknownWord(Some(UnknownToken::new("40000", 3)));
+
+        let result = parser.try_parse_instructions("1 40000 +".to_string(), &word_manager);
+        let lenient_result = parser.parse_instructions("1 40000 +".to_string(), &word_manager);
+
+        assert_eq!(result, Err(expected_error.into()));
+        assert_eq!(
+            lenient_result,
+            vec![
+                Instruction::number(1),
+                Instruction::operator("+".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn can_parse_spans_of_multiline_input() {
+        let parser = Parser::new();
+        let word_manager = WordDefinitionManager::new();
+        let input = String::from(": foo\n  dup +\n;");
+        let expected_spans = vec![
+            Span::new(4, 1, ":"),
+            Span::new(4, 3, "foo"),
+            Span::new(5, 3, "dup"),
+            Span::new(5, 7, "+"),
+            Span::new(6, 1, ";"),
+        ];
+
+        let (instructions, spans) = parser.parse_with_spans(input, &word_manager, 4).unwrap();
+
+        assert_eq!(instructions.len(), spans.len());
+        assert_eq!(spans, expected_spans);
+    }
 }
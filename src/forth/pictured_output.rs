@@ -0,0 +1,110 @@
+use crate::cell::{Cell, numeric_base};
+
+/// # PicturedOutput struct
+///
+/// This struct holds the string being built by the pictured numeric output words.
+/// The string is built from right to left: `<#` starts an empty one, `#` and `#S` add the digits
+/// of an unsigned double-cell number from the least significant one, `HOLD` adds a character
+/// and `SIGN` adds a minus sign. `#>` gets the result.
+///
+/// # Examples
+/// ```
+///# use rust_forth::forth::pictured_output::PicturedOutput;
+/// let mut pictured = PicturedOutput::new();
+/// pictured.begin();
+/// let rest = pictured.digit(1234, 10);
+/// let rest = pictured.digit(rest, 10);
+/// pictured.hold('.');
+/// pictured.digits(rest, 10);
+/// pictured.sign(-1);
+///
+/// assert_eq!(pictured.finish(), "-12.34");
+/// ```
+#[derive(Debug, PartialEq, Default)]
+pub struct PicturedOutput {
+    buffer: Vec<char>,
+}
+
+impl PicturedOutput {
+    pub fn new() -> Self {
+        PicturedOutput { buffer: Vec::new() }
+    }
+
+    /// Starts a new pictured string (`<#`).
+    pub fn begin(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Adds a character to the beginning of the string (`HOLD`).
+    pub fn hold(&mut self, character: char) {
+        self.buffer.push(character);
+    }
+
+    /// Adds the least significant digit of a number in the given base (`#`).
+    /// Returns the number without that digit.
+    pub fn digit(&mut self, number: u128, base: u32) -> u128 {
+        let base = numeric_base(base as Cell);
+        let digit = (number % base as u128) as u32;
+        let character = char::from_digit(digit, base).unwrap_or('?');
+        self.hold(character.to_ascii_uppercase());
+        number / base as u128
+    }
+
+    /// Adds every digit of a number in the given base, at least one (`#S`).
+    /// Returns zero, the number without its digits.
+    pub fn digits(&mut self, mut number: u128, base: u32) -> u128 {
+        loop {
+            number = self.digit(number, base);
+            if number == 0 {
+                return number;
+            }
+        }
+    }
+
+    /// Adds a minus sign if the given number is negative (`SIGN`).
+    pub fn sign(&mut self, number: Cell) {
+        if number < 0 {
+            self.hold('-');
+        }
+    }
+
+    /// Returns the string built so far (`#>`).
+    pub fn finish(&self) -> String {
+        self.buffer.iter().rev().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_are_added_from_the_least_significant_one() {
+        let mut pictured = PicturedOutput::new();
+
+        let rest = pictured.digit(255, 16);
+
+        assert_eq!(rest, 15);
+        assert_eq!(pictured.finish(), "F");
+    }
+
+    #[test]
+    fn all_digits_add_at_least_one_digit() {
+        let mut pictured = PicturedOutput::new();
+
+        assert_eq!(pictured.digits(0, 10), 0);
+        assert_eq!(pictured.finish(), "0");
+    }
+
+    #[test]
+    fn begin_discards_the_previous_string() {
+        let mut pictured = PicturedOutput::new();
+        pictured.digits(42, 10);
+
+        pictured.begin();
+        pictured.digits(5, 2);
+        pictured.sign(0);
+
+        assert_eq!(pictured.finish(), "101");
+    }
+}
@@ -0,0 +1,18 @@
+/// Position of a token in the source code.
+/// Lines and columns start at 1, and `word` keeps the text of the token.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub word: String,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, word: impl Into<String>) -> Self {
+        Span {
+            line,
+            column,
+            word: word.into(),
+        }
+    }
+}
@@ -1,19 +1,26 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::vec;
 
-use crate::errors::Error;
+use crate::cell::{Cell, CellSize};
+use crate::errors::{Error, Location};
 use crate::forth::definition_type::DefinitionType;
 use crate::forth::forth_errors::ForthError;
 use crate::forth::intruction::Instruction;
 use crate::forth::word_data::WordData;
 use crate::handler::instructions_handler::ExecutionHandler;
+use crate::memory::memory_operations::{FETCH, MemoryOperation, STORE};
 use crate::stack::stack_operations::StackOperation;
 use crate::{BooleanOperation, LogicalOperation};
 
-use super::boolean_operations::{FORTH_FALSE, FORTH_TRUE};
-use super::definition_type::{ELSE, IF, THEN};
-use super::output_instructions::{CR, DOT, EMIT, OutputInstruction};
+use super::boolean_operations::FORTH_FALSE;
+use super::data_definition::{DataDefinition, TO};
+use super::definition_type::{
+    AGAIN, BEGIN, DO, ELSE, EXIT, IF, LEAVE, LOOP, LOOP_INDEX, OUTER_LOOP_INDEX, PLUS_LOOP, REPEAT,
+    THEN, UNLOOP, UNTIL, WHILE,
+};
+use super::output_instructions::OutputInstruction;
+use super::span::Span;
 
 /// Enum that represents a word in the Forth language.
 /// It can be either a predefined word (like "DUP") or a user-defined word (like "MY_WORD").
@@ -24,16 +31,77 @@ pub enum WordType {
     UserDefined(String),
 }
 
+/// Policy used to compare the names of user-defined words:
+/// - Insensitive: `Square`, `SQUARE` and `square` are the same word, as in standard Forth.
+/// - Sensitive: Names only match when they are written the same way.
+///
+/// Either way, the names keep the spelling of their definition to be shown by `WORDS` and `SEE`.
+///
+/// # Examples
+/// ```rust
+///# use rust_forth::forth::word::CaseSensitivity;
+/// assert_eq!(CaseSensitivity::Insensitive.fold("Square"), "square");
+/// assert_eq!(CaseSensitivity::Sensitive.fold("Square"), "Square");
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum CaseSensitivity {
+    #[default]
+    Insensitive,
+    Sensitive,
+}
+
+impl CaseSensitivity {
+    /// Folds a name to the form in which it is looked up in the dictionary.
+    pub fn fold(&self, name: &str) -> String {
+        match self {
+            CaseSensitivity::Insensitive => name.to_lowercase(),
+            CaseSensitivity::Sensitive => name.to_string(),
+        }
+    }
+}
+
+/// Number of return stack cells used by the parameters of a counted loop (limit and index).
+const LOOP_FRAME_SIZE: usize = 2;
+
+/// Name of the definitions compiled to execute control structures outside of a word.
+/// It can not be the name of a user-defined word.
+const ANONYMOUS_DEFINITION: &str = "";
+
+/// Default maximum number of nested calls to user-defined words.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 256;
+
+/// Indicates how the execution continues after a block of instructions is executed.
+/// `Leave` exits the innermost counted loop and `Exit` returns from the current word.
+#[derive(Debug, PartialEq)]
+enum ControlFlow {
+    Next,
+    Leave,
+    Exit,
+}
+
 /// Struct that represents a word manager in the Forth interpreter
 ///
 /// The `WordDefinitionManager` is responsible for managing the definitions of words in the Forth language.
-/// It stores the definitions of words, their execution stack, and the current nesting level.
+/// It stores the definitions of words, with the stack effect written in their comment if any,
+/// indexed by their name as folded by its case sensitivity policy,
+/// and their execution stack, which holds the chain of
+/// user-defined words being executed, from the outermost to the innermost,
+/// as well as the chain that was being executed when the last error was raised,
+/// and the names that were not defined words when the last definition was compiled.
 /// It also provides methods for defining new words, executing words, and checking if a word is defined.
 pub struct WordDefinitionManager {
     words: HashMap<WordType, usize>,
     definitions: Vec<Vec<WordData>>,
+    definition_names: Vec<String>,
+    definition_spans: Vec<Vec<Span>>,
+    stack_effects: Vec<Option<String>>,
     execution_stack: Vec<WordType>,
-    nesting_level: usize,
+    traceback: Vec<String>,
+    undefined_names: Vec<String>,
+    values: HashSet<usize>,
+    markers: HashSet<usize>,
+    max_call_depth: usize,
+    case_sensitivity: CaseSensitivity,
 }
 
 impl Default for WordDefinitionManager {
@@ -48,8 +116,46 @@ impl WordDefinitionManager {
         WordDefinitionManager {
             words: HashMap::new(),
             definitions: Vec::new(),
+            definition_names: Vec::new(),
+            definition_spans: Vec::new(),
+            stack_effects: Vec::new(),
             execution_stack: Vec::new(),
-            nesting_level: 0,
+            traceback: Vec::new(),
+            undefined_names: Vec::new(),
+            values: HashSet::new(),
+            markers: HashSet::new(),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            case_sensitivity: CaseSensitivity::default(),
+        }
+    }
+
+    /// Sets the maximum number of nested calls to user-defined words.
+    /// If not provided, the default maximum is used.
+    /// Exceeding it raises `ForthError::MaxCallDepthExceeded` instead of
+    /// overflowing the stack of the interpreter.
+    pub fn with_max_call_depth(mut self, max_call_depth: Option<usize>) -> Self {
+        self.max_call_depth = max_call_depth.unwrap_or(DEFAULT_MAX_CALL_DEPTH);
+        self
+    }
+
+    /// Sets how the names of user-defined words are compared.
+    /// By default they are case insensitive.
+    pub fn with_case_sensitivity(mut self, case_sensitivity: CaseSensitivity) -> Self {
+        self.case_sensitivity = case_sensitivity;
+        self
+    }
+
+    /// Folds the name of a user-defined word according to the case sensitivity policy,
+    /// so that two names refer to the same word if and only if their folded forms are equal.
+    pub fn fold_name(&self, name: &str) -> String {
+        self.case_sensitivity.fold(name)
+    }
+
+    /// Gets the key of a word in the dictionary.
+    fn key(&self, name: &WordType) -> WordType {
+        match name {
+            WordType::Predefined(name) => WordType::Predefined(name),
+            WordType::UserDefined(name) => WordType::UserDefined(self.fold_name(name)),
         }
     }
 
@@ -83,45 +189,136 @@ impl WordDefinitionManager {
     /// assert_eq!(definition.unwrap(), &vec![WordData::Number(5), WordData::Operator("+".to_string())]);
     /// ```
     pub fn define_new_word(&mut self, name: WordType, body: Vec<Instruction>) -> Result<(), Error> {
-        if let WordType::UserDefined(ref name_str) = name {
-            if !self.is_word_name_valid(name_str) {
-                return Err(ForthError::InvalidWord.into());
-            }
+        self.define_new_word_with_spans(name, body, Vec::new())
+    }
+
+    /// Defines a new word in the Forth interpreter, keeping the span of the token that
+    /// produced each instruction of its body, so that errors raised while it is executed
+    /// can be located.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The name of the word to be defined.
+    /// - `body` - The body of instructions that make up the word definition.
+    /// - `spans` - The span of each instruction of the body.
+    pub fn define_new_word_with_spans(
+        &mut self,
+        name: WordType,
+        body: Vec<Instruction>,
+        spans: Vec<Span>,
+    ) -> Result<(), Error> {
+        if let WordType::UserDefined(ref name_str) = name
+            && !self.is_word_name_valid(name_str)
+        {
+            return Err(ForthError::InvalidWord.into());
         }
 
+        let index = self.definitions.len();
+        let stack_effect = find_stack_effect(&body);
+        let (definition, definition_spans) = self.compile(body, spans, &name, index)?;
+
+        let word_name = match name {
+            WordType::Predefined(name) => name.to_string(),
+            WordType::UserDefined(ref name) => name.to_string(),
+        };
+        self.definitions.push(definition);
+        self.definition_names.push(word_name);
+        self.definition_spans.push(definition_spans);
+        self.stack_effects.push(stack_effect);
+        self.words.insert(self.key(&name), index);
+        Ok(())
+    }
+
+    /// Compiles and executes instructions that are interpreted outside of a definition
+    /// but contain control structures, as `1 IF 10 THEN` or `3 0 DO I . LOOP`.
+    /// They are compiled into an anonymous definition that is discarded once executed.
+    /// Errors are located at the token that raised them, as the ones of interpreted code.
+    ///
+    /// # Arguments
+    ///
+    /// - `handler` - The instruction handler that manages the execution of instructions.
+    /// - `body` - The instructions to be executed, ending with `EndDefinition`.
+    /// - `spans` - The span of each instruction of the body.
+    pub fn run_anonymous<W: Write>(
+        &mut self,
+        handler: &mut ExecutionHandler<W>,
+        body: Vec<Instruction>,
+        spans: Vec<Span>,
+    ) -> Result<(), Error> {
+        let name = WordType::Predefined(ANONYMOUS_DEFINITION);
+        let index = self.definitions.len();
+        let (definition, definition_spans) = self.compile(body, spans, &name, index)?;
+
+        self.definitions.push(definition);
+        self.definition_names.push(ANONYMOUS_DEFINITION.to_string());
+        self.definition_spans.push(definition_spans);
+        self.stack_effects.push(None);
+        self.execution_stack.clear();
+        self.traceback.clear();
+        let result = self.execute_instruction(handler, index, 0);
+        self.definitions.truncate(index);
+        self.definition_names.truncate(index);
+        self.definition_spans.truncate(index);
+        self.stack_effects.truncate(index);
+        result
+    }
+
+    /// Compiles the body of a definition, up to its `EndDefinition`, into the data
+    /// of the definition at `index`, together with the span of each compiled element.
+    /// Definitions with unbalanced control structures are rejected.
+    fn compile(
+        &mut self,
+        body: Vec<Instruction>,
+        spans: Vec<Span>,
+        name: &WordType,
+        index: usize,
+    ) -> Result<(Vec<WordData>, Vec<Span>), Error> {
         let end_index = find_end_definition(&body).ok_or(ForthError::InvalidWord)?;
         let word_definition = body.into_iter().take(end_index).collect::<Vec<_>>();
         let mut definition: Vec<WordData> = Vec::new();
+        let mut definition_spans = Vec::new();
+        self.undefined_names.clear();
 
-        for element in word_definition {
-            definition.extend(self.convert_to_word_definition(element)?);
+        for (position, element) in word_definition.into_iter().enumerate() {
+            definition.extend(self.convert_to_word_definition(element, name, index)?);
+            if let Some(span) = spans.get(position) {
+                definition_spans.resize(definition.len(), span.clone());
+            }
         }
-
-        let index = self.definitions.len();
-        self.definitions.push(definition);
-        self.words.insert(name, index);
-        Ok(())
+        check_control_structures(&definition)?;
+        Ok((definition, definition_spans))
     }
 
     /// Converts a Forth instruction into a word definition.
     /// This function is used to expand the definition of a word into its individual components.
+    /// `name` and `index` identify the definition being compiled, so that it can call itself.
     fn convert_to_word_definition(
         &mut self,
         instruction: Instruction,
+        name: &WordType,
+        index: usize,
     ) -> Result<Vec<WordData>, Error> {
         match instruction {
             Instruction::Number(number) => self.convert_number(number),
             Instruction::Operator(operator) => self.convert_operator(operator),
             Instruction::StackWord(stack_word) => self.convert_stack_word(stack_word),
-            Instruction::DefinitionType(define_word) => self.convert_define_word(define_word),
+            Instruction::DefinitionType(define_word) => {
+                self.convert_define_word(define_word, name, index)
+            }
             Instruction::BooleanOperation(bool_op) => self.convert_boolean_operation(bool_op),
             Instruction::LogicalOperation(log_op) => self.convert_logical_operation(log_op),
             Instruction::Output(output) => self.convert_output_instruction(output),
+            Instruction::MemoryWord(memory_word) => self.convert_memory_word(memory_word),
+            Instruction::DataDefinition(definition, name) => {
+                self.convert_data_definition(definition, name)
+            }
+            Instruction::StringWord(string_word) => Ok(vec![WordData::string_word(string_word)]),
+            Instruction::DictionaryWord(..) => Err(ForthError::InterpretOnlyWord.into()),
             _ => Ok(vec![]),
         }
     }
 
-    fn convert_number(&self, number: i16) -> Result<Vec<WordData>, Error> {
+    fn convert_number(&self, number: Cell) -> Result<Vec<WordData>, Error> {
         Ok(vec![WordData::number(number)])
     }
 
@@ -133,21 +330,71 @@ impl WordDefinitionManager {
         Ok(vec![WordData::stack_word(stack_word)])
     }
 
-    fn convert_define_word(&self, define_word: DefinitionType) -> Result<Vec<WordData>, Error> {
+    /// Calls to other words are resolved to the index of their current definition.
+    /// The name of the word being defined follows the same rule: when the word was defined
+    /// before, it calls that previous definition, so that `: foo foo 1+ ;` extends `foo`,
+    /// and only otherwise it calls the definition being compiled, as `RECURSE` always does.
+    /// Other names that are not defined words raise `ForthError::UnknownWord` in the
+    /// control structures that are interpreted, as they do outside them, and are compiled
+    /// to nothing in definitions, being kept for `take_undefined_names`.
+    fn convert_define_word(
+        &mut self,
+        define_word: DefinitionType,
+        current_name: &WordType,
+        current_index: usize,
+    ) -> Result<Vec<WordData>, Error> {
         let mut definition = Vec::new();
         match define_word {
             DefinitionType::Name(name) => {
-                if self.is_word_defined(&WordType::UserDefined(name.to_string())) {
-                    if let Some(&index) = self.words.get(&WordType::UserDefined(name.to_string())) {
-                        definition.push(WordData::DefinitionIndex(index));
+                let word = self.key(&WordType::UserDefined(name.to_string()));
+                if let Some(&index) = self.words.get(&word) {
+                    if self.markers.contains(&index) {
+                        return Err(ForthError::InvalidWord.into());
                     }
+                    definition.push(WordData::DefinitionIndex(index));
+                } else if word == self.key(current_name) {
+                    definition.push(WordData::DefinitionIndex(current_index));
+                } else if *current_name == WordType::Predefined(ANONYMOUS_DEFINITION) {
+                    return Err(ForthError::UnknownWord(None).into());
+                } else {
+                    self.undefined_names.push(name);
                 }
             }
-            IF | THEN | ELSE => definition.push(WordData::definition_type(define_word)),
+            DefinitionType::Recurse
+                if *current_name == WordType::Predefined(ANONYMOUS_DEFINITION) =>
+            {
+                return Err(ForthError::CompileOnlyWord.into());
+            }
+            DefinitionType::Recurse => definition.push(WordData::DefinitionIndex(current_index)),
+            _ => definition.push(WordData::definition_type(define_word)),
         }
         Ok(definition)
     }
 
+    fn convert_memory_word(&self, memory_word: MemoryOperation) -> Result<Vec<WordData>, Error> {
+        Ok(vec![WordData::memory_word(memory_word)])
+    }
+
+    /// Only `TO` can be compiled inside a definition: it stores into the cell of the value,
+    /// which is resolved when the word is defined.
+    fn convert_data_definition(
+        &self,
+        definition: DataDefinition,
+        name: String,
+    ) -> Result<Vec<WordData>, Error> {
+        if definition != TO {
+            return Err(ForthError::InvalidWord.into());
+        }
+
+        let address = self
+            .value_address(&WordType::UserDefined(name))
+            .ok_or(ForthError::InvalidWord)?;
+        Ok(vec![
+            WordData::number(address),
+            WordData::memory_word(STORE),
+        ])
+    }
+
     fn convert_boolean_operation(&self, bool_op: BooleanOperation) -> Result<Vec<WordData>, Error> {
         Ok(vec![WordData::boolean_operation(bool_op)])
     }
@@ -160,14 +407,7 @@ impl WordDefinitionManager {
         &self,
         instruction: OutputInstruction,
     ) -> Result<Vec<WordData>, Error> {
-        match instruction {
-            DOT => Ok(vec![WordData::output(DOT)]),
-            EMIT => Ok(vec![WordData::output(EMIT)]),
-            CR => Ok(vec![WordData::output(CR)]),
-            OutputInstruction::DotQuote(str) => {
-                Ok(vec![WordData::output(OutputInstruction::dot_quote(str))])
-            }
-        }
+        Ok(vec![WordData::output(instruction)])
     }
 
     /// Executes a word in the Forth interpreter.
@@ -206,27 +446,76 @@ impl WordDefinitionManager {
         handler: &mut ExecutionHandler<W>,
         word_name: &str,
     ) -> Result<(), Error> {
-        self.execution_stack
-            .push(WordType::UserDefined(word_name.to_string()));
+        let index = *self
+            .words
+            .get(&self.key(&WordType::UserDefined(word_name.to_string())))
+            .ok_or(ForthError::UnknownWord(None))?;
+
+        self.execution_stack.clear();
+        self.traceback.clear();
+        self.call_definition(handler, index)
+    }
 
-        while let Some(current_word) = self.execution_stack.pop() {
-            let index = self
-                .words
-                .get(&current_word)
-                .ok_or(ForthError::UnknownWord)?;
+    /// Executes a definition as a nested call, keeping track of the chain of words being executed.
+    /// Returns `ForthError::MaxCallDepthExceeded` if the maximum depth is reached.
+    /// When an error is raised inside the definition, the chain of words being executed
+    /// is kept to be returned by `take_traceback`.
+    fn call_definition<W: Write>(
+        &mut self,
+        handler: &mut ExecutionHandler<W>,
+        def_index: usize,
+    ) -> Result<(), Error> {
+        if self.execution_stack.len() >= self.max_call_depth {
+            return Err(ForthError::MaxCallDepthExceeded.into());
+        }
 
-            self.execute_instruction(handler, *index, 0)?;
+        let name = self
+            .definition_names
+            .get(def_index)
+            .map_or_else(String::new, |name| name.to_string());
+        self.execution_stack.push(WordType::UserDefined(name));
+        let result = self.execute_instruction(handler, def_index, 0);
+        if result.is_err() && self.traceback.is_empty() {
+            self.traceback = self.call_chain();
         }
+        self.execution_stack.pop();
+        result
+    }
 
-        self.execution_stack.clear();
-        Ok(())
+    /// Takes the names of the words that were being executed when the last error was raised,
+    /// from the innermost to the outermost.
+    /// It is empty if the error was not raised inside a user-defined word.
+    pub fn take_traceback(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.traceback)
+    }
+
+    /// Takes the names that were not defined words when the last definition was compiled,
+    /// which were compiled to nothing, so that they can be reported.
+    pub fn take_undefined_names(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.undefined_names)
     }
 
+    /// Returns the names of the words being executed, from the innermost to the outermost.
+    fn call_chain(&self) -> Vec<String> {
+        self.execution_stack
+            .iter()
+            .rev()
+            .map(|word| match word {
+                WordType::Predefined(name) => name.to_string(),
+                WordType::UserDefined(name) => name.to_string(),
+            })
+            .collect()
+    }
+
+    /// Finds the index of the instruction that matches one of the `targets`
+    /// for the control structure that is open right before `start`.
+    /// Nested control structures (IF ... THEN, DO ... LOOP, BEGIN ... UNTIL) are skipped,
+    /// and the search stops when the enclosing structure is closed.
     fn find_instruction_index(
         &self,
         def_index: usize,
         start: usize,
-        target: WordData,
+        targets: &[DefinitionType],
     ) -> Option<usize> {
         let instructions = self
             .definitions
@@ -236,20 +525,20 @@ impl WordDefinitionManager {
 
         let mut nesting_level = 0;
         for (offset, instruction) in instructions.iter().enumerate() {
-            match *instruction {
-                WordData::DefinitionType(IF) => nesting_level += 1,
-                WordData::DefinitionType(THEN) => {
-                    if nesting_level == 0 && target == WordData::DefinitionType(THEN) {
-                        return Some(start + offset);
-                    }
-                    nesting_level -= 1;
-                }
-                WordData::DefinitionType(ELSE) => {
-                    if nesting_level == 0 && target == WordData::DefinitionType(ELSE) {
-                        return Some(start + offset);
-                    }
+            let WordData::DefinitionType(definition_type) = instruction else {
+                continue;
+            };
+
+            if nesting_level == 0 && targets.contains(definition_type) {
+                return Some(start + offset);
+            }
+            if definition_type.opens_block() {
+                nesting_level += 1;
+            } else if definition_type.closes_block() {
+                if nesting_level == 0 {
+                    return None;
                 }
-                _ => {}
+                nesting_level -= 1;
             }
         }
         None
@@ -270,80 +559,350 @@ impl WordDefinitionManager {
         def_index: usize,
         instruction_index: usize,
     ) -> Result<(), Error> {
-        let mut i = instruction_index;
-        while let Some(instruction) = self.definitions.get(def_index).and_then(|def| def.get(i)) {
-            match &instruction {
-                WordData::DefinitionType(DefinitionType::Name(name)) => {
-                    self.execution_stack
-                        .push(WordType::UserDefined(name.to_string()));
-                }
-                WordData::DefinitionIndex(index) => {
-                    self.execute_instruction(handler, *index, 0)?;
-                }
-                WordData::DefinitionType(IF) => {
-                    i = self.execute_if(handler, def_index, i)?;
-                }
-                WordData::DefinitionType(ELSE) => {
-                    if self.nesting_level > 0 {
-                        break;
-                    }
+        let end = self.definitions.get(def_index).map_or(0, |def| def.len());
+        self.execute_block(handler, def_index, instruction_index, end)?;
+        Ok(())
+    }
+
+    /// Executes the instructions of a definition in the range `start..end`.
+    /// Returns how the execution should continue once the block is finished,
+    /// so that `LEAVE` and `EXIT` can escape from nested control structures.
+    /// Errors are located at the instruction that raised them.
+    fn execute_block<W: Write>(
+        &mut self,
+        handler: &mut ExecutionHandler<W>,
+        def_index: usize,
+        start: usize,
+        end: usize,
+    ) -> Result<ControlFlow, Error> {
+        let mut i = start;
+        while i < end {
+            let (flow, last_index) = self
+                .execute_step(handler, def_index, i)
+                .map_err(|error| self.locate_error(error, def_index, i))?;
+            if flow != ControlFlow::Next {
+                return Ok(flow);
+            }
+            i = last_index + 1;
+        }
+        Ok(ControlFlow::Next)
+    }
+
+    /// Executes the instruction at `instruction_index`, together with the control
+    /// structure it opens, if any.
+    /// Returns how the execution should continue and the index of the last instruction executed.
+    fn execute_step<W: Write>(
+        &mut self,
+        handler: &mut ExecutionHandler<W>,
+        def_index: usize,
+        instruction_index: usize,
+    ) -> Result<(ControlFlow, usize), Error> {
+        let i = instruction_index;
+        let Some(instruction) = self.definitions.get(def_index).and_then(|def| def.get(i)) else {
+            return Ok((ControlFlow::Next, i));
+        };
+
+        match &instruction {
+            WordData::DefinitionIndex(index) => {
+                self.call_definition(handler, *index)?;
+            }
+            WordData::DefinitionType(IF) => return self.execute_if(handler, def_index, i),
+            WordData::DefinitionType(DO) => {
+                let (flow, loop_index) = self.execute_do(handler, def_index, i)?;
+                if flow == ControlFlow::Exit {
+                    return Ok((flow, loop_index));
                 }
-                WordData::DefinitionType(THEN) => {
-                    self.execute_then()?;
-                    if self.nesting_level > 0 {
-                        break;
-                    }
+                return Ok((ControlFlow::Next, loop_index));
+            }
+            WordData::DefinitionType(BEGIN) => return self.execute_begin(handler, def_index, i),
+            WordData::DefinitionType(LOOP_INDEX) => {
+                let index = *handler.handle_get_return_element(0)?;
+                handler.handle_push_element(index)?;
+            }
+            WordData::DefinitionType(OUTER_LOOP_INDEX) => {
+                let index = *handler.handle_get_return_element(LOOP_FRAME_SIZE)?;
+                handler.handle_push_element(index)?;
+            }
+            WordData::DefinitionType(LEAVE) => return Ok((ControlFlow::Leave, i)),
+            WordData::DefinitionType(UNLOOP) => {
+                handler.handle_get_return_element(LOOP_FRAME_SIZE - 1)?;
+                for _ in 0..LOOP_FRAME_SIZE {
+                    handler.handle_drop_return_element()?;
                 }
-                _ => handler.handle_word_instruction(instruction)?,
             }
-            i += 1;
+            WordData::DefinitionType(EXIT) => return Ok((ControlFlow::Exit, i)),
+            _ => handler.handle_word_instruction(instruction)?,
+        }
+        Ok((ControlFlow::Next, i))
+    }
+
+    /// Attaches to an error the location of the instruction of a definition that raised it,
+    /// naming the word that contains the instruction, or the instruction itself
+    /// for anonymous definitions.
+    /// Definitions compiled without spans leave the error as it is.
+    fn locate_error(&self, error: Error, def_index: usize, instruction_index: usize) -> Error {
+        let span = self
+            .definition_spans
+            .get(def_index)
+            .and_then(|spans| spans.get(instruction_index));
+        match (span, self.definition_names.get(def_index)) {
+            (Some(span), Some(name)) if name == ANONYMOUS_DEFINITION => {
+                error.locate(Location::new(span, span.word.to_string()))
+            }
+            (Some(span), Some(name)) => error.locate(Location::new(span, name.to_string())),
+            _ => error,
         }
-        Ok(())
     }
 
     /// Handles the `IF` instruction in the Forth interpreter.
+    /// Executes the `IF` or the `ELSE` branch depending on the condition on top of the stack,
+    /// and returns the index of the matching `THEN` to continue from.
     fn execute_if<W: Write>(
         &mut self,
         handler: &mut ExecutionHandler<W>,
         def_index: usize,
         instruction_index: usize,
-    ) -> Result<usize, Error> {
-        let then_index = self.find_instruction_index(
-            def_index,
-            instruction_index + 1,
-            WordData::DefinitionType(THEN),
-        );
-        let else_index = self.find_instruction_index(
-            def_index,
-            instruction_index + 1,
-            WordData::DefinitionType(ELSE),
-        );
+    ) -> Result<(ControlFlow, usize), Error> {
+        let then_index = self
+            .find_instruction_index(def_index, instruction_index + 1, &[THEN])
+            .ok_or(ForthError::UnbalancedControlStructure)?;
+        let else_index = self.find_instruction_index(def_index, instruction_index + 1, &[ELSE]);
         let condition = handler.handle_drop_element()?;
 
-        if let Some(then_index) = then_index {
-            self.nesting_level += 1;
-            if condition == FORTH_TRUE || condition != FORTH_FALSE {
-                self.execute_instruction(handler, def_index, instruction_index + 1)?;
-            } else if let Some(else_index) = else_index {
-                self.execute_instruction(handler, def_index, else_index + 1)?;
+        let flow = if condition != FORTH_FALSE {
+            let end = else_index.unwrap_or(then_index);
+            self.execute_block(handler, def_index, instruction_index + 1, end)?
+        } else if let Some(else_index) = else_index {
+            self.execute_block(handler, def_index, else_index + 1, then_index)?
+        } else {
+            ControlFlow::Next
+        };
+        Ok((flow, then_index))
+    }
+
+    /// Handles the `DO` instruction in the Forth interpreter.
+    /// Takes the limit and the initial index from the stack and moves them to the return stack,
+    /// where `I` and `J` read them, then executes the body until the index crosses
+    /// the boundary between `limit - 1` and `limit`.
+    /// Returns the index of the matching `LOOP` or `+LOOP` to continue from.
+    fn execute_do<W: Write>(
+        &mut self,
+        handler: &mut ExecutionHandler<W>,
+        def_index: usize,
+        instruction_index: usize,
+    ) -> Result<(ControlFlow, usize), Error> {
+        let loop_index = self
+            .find_instruction_index(def_index, instruction_index + 1, &[LOOP, PLUS_LOOP])
+            .ok_or(ForthError::UnbalancedControlStructure)?;
+        let index = handler.handle_drop_element()?;
+        let limit = handler.handle_drop_element()?;
+
+        let depth = handler.handle_return_stack_size();
+        handler.handle_push_return_element(limit)?;
+        handler.handle_push_return_element(index)?;
+
+        let result = self.run_counted_loop(handler, def_index, instruction_index, loop_index);
+        handler.handle_truncate_return_stack(depth);
+        Ok((result?, loop_index))
+    }
+
+    /// Runs the body of a counted loop whose parameters are on top of the return stack,
+    /// updating the index after each iteration.
+    fn run_counted_loop<W: Write>(
+        &mut self,
+        handler: &mut ExecutionHandler<W>,
+        def_index: usize,
+        do_index: usize,
+        loop_index: usize,
+    ) -> Result<ControlFlow, Error> {
+        let depth = handler.handle_return_stack_size();
+        let is_plus_loop = matches!(
+            self.definitions[def_index][loop_index],
+            WordData::DefinitionType(PLUS_LOOP)
+        );
+
+        loop {
+            let flow = self.execute_block(handler, def_index, do_index + 1, loop_index)?;
+            if flow != ControlFlow::Next {
+                return Ok(flow);
+            }
+
+            let step = if is_plus_loop {
+                handler.handle_drop_element()?
+            } else {
+                1
+            };
+
+            if handler.handle_return_stack_size() < depth {
+                return Ok(ControlFlow::Next);
+            }
+            let previous = handler.handle_drop_return_element()?;
+            let limit = *handler.handle_get_return_element(0)?;
+            let cell_size = handler.handle_cell_size();
+            let current = cell_size.wrap(previous as i128 + step as i128);
+            handler.handle_push_return_element(current)?;
+
+            if crosses_loop_limit(previous, current, limit, cell_size) {
+                return Ok(ControlFlow::Next);
             }
-            return Ok(then_index);
         }
+    }
+
+    /// Handles the `BEGIN` instruction in the Forth interpreter.
+    /// Repeats the body until the flag taken by `UNTIL` is true, while the flag taken
+    /// by `WHILE` is true, or forever with `AGAIN` (until `LEAVE` or `EXIT` is reached).
+    /// Returns the index of the matching `UNTIL`, `REPEAT` or `AGAIN` to continue from.
+    fn execute_begin<W: Write>(
+        &mut self,
+        handler: &mut ExecutionHandler<W>,
+        def_index: usize,
+        instruction_index: usize,
+    ) -> Result<(ControlFlow, usize), Error> {
+        let body_start = instruction_index + 1;
+        let end_index = self
+            .find_instruction_index(def_index, body_start, &[UNTIL, REPEAT, AGAIN])
+            .ok_or(ForthError::UnbalancedControlStructure)?;
+        let while_index = self.find_instruction_index(def_index, body_start, &[WHILE]);
+
+        let flow = match self.definitions[def_index][end_index] {
+            WordData::DefinitionType(UNTIL) => loop {
+                let flow = self.execute_block(handler, def_index, body_start, end_index)?;
+                if flow != ControlFlow::Next {
+                    break flow;
+                }
+                if handler.handle_drop_element()? != FORTH_FALSE {
+                    break ControlFlow::Next;
+                }
+            },
+            WordData::DefinitionType(REPEAT) => {
+                let while_index = while_index.ok_or(ForthError::UnbalancedControlStructure)?;
+                loop {
+                    let flow = self.execute_block(handler, def_index, body_start, while_index)?;
+                    if flow != ControlFlow::Next {
+                        break flow;
+                    }
+                    if handler.handle_drop_element()? == FORTH_FALSE {
+                        break ControlFlow::Next;
+                    }
+                    let flow =
+                        self.execute_block(handler, def_index, while_index + 1, end_index)?;
+                    if flow != ControlFlow::Next {
+                        break flow;
+                    }
+                }
+            }
+            _ => loop {
+                let flow = self.execute_block(handler, def_index, body_start, end_index)?;
+                if flow != ControlFlow::Next {
+                    break flow;
+                }
+            },
+        };
+        Ok((flow, end_index))
+    }
 
-        Err(ForthError::InvalidWord.into())
+    /// Defines a word created with `VALUE`, which pushes the content of the cell at `address`.
+    /// The address can be retrieved later with `value_address` to change the value with `TO`.
+    pub fn define_value(&mut self, name: WordType, address: Cell) -> Result<(), Error> {
+        let body = vec![
+            Instruction::number(address),
+            Instruction::memory_word(FETCH),
+            Instruction::end_definition(),
+        ];
+        self.define_new_word(name, body)?;
+        self.values.insert(self.definitions.len() - 1);
+        Ok(())
     }
 
-    /// Handles the `THEN` instruction in the Forth interpreter.
-    fn execute_then(&mut self) -> Result<(), Error> {
-        if self.nesting_level > 0 {
-            self.nesting_level -= 1;
+    /// Gets the address of the cell that holds the content of a word created with `VALUE`.
+    /// If the word is not defined or is not a value, it returns `None`.
+    pub fn value_address(&self, name: &WordType) -> Option<Cell> {
+        let index = self.words.get(&self.key(name))?;
+        if !self.values.contains(index) {
+            return None;
+        }
+
+        match self.definitions.get(*index)?.first()? {
+            WordData::Number(address) => Some(*address),
+            _ => None,
         }
+    }
+
+    /// Defines a word created with `MARKER`, which forgets itself and every word defined after it
+    /// when it is executed with `forget`. Markers can not be used inside other definitions.
+    pub fn define_marker(&mut self, name: WordType) -> Result<(), Error> {
+        self.define_new_word(name, vec![Instruction::end_definition()])?;
+        self.markers.insert(self.definitions.len() - 1);
         Ok(())
     }
 
+    /// Checks if a word was created with `MARKER`.
+    pub fn is_marker(&self, name: &WordType) -> bool {
+        self.words
+            .get(&self.key(name))
+            .is_some_and(|index| self.markers.contains(index))
+    }
+
+    /// Removes the current definition of a word and every definition made after it,
+    /// as `FORGET` does. The words that were redefined since then get back their previous
+    /// definition, which was kept by the definitions that referred to it.
+    /// Returns the number of definitions that are kept, which is the index of the forgotten one.
+    ///
+    /// If the word is not defined, it returns `ForthError::UnknownWord`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    ///# use rust_forth::forth::word::{WordDefinitionManager, WordType};
+    ///# use rust_forth::forth::intruction::Instruction;
+    ///# use rust_forth::forth::word_data::WordData;
+    /// let mut word_manager = WordDefinitionManager::new();
+    /// let foo = WordType::UserDefined("foo".to_string());
+    /// let body = |number| vec![Instruction::Number(number), Instruction::EndDefinition];
+    /// let _ = word_manager.define_new_word(WordType::UserDefined("foo".to_string()), body(1));
+    /// let _ = word_manager.define_new_word(WordType::UserDefined("foo".to_string()), body(2));
+    ///
+    /// assert_eq!(word_manager.forget(&foo), Ok(1));
+    /// assert_eq!(word_manager.get_word_definition(&foo), Some(&vec![WordData::Number(1)]));
+    /// ```
+    pub fn forget(&mut self, name: &WordType) -> Result<usize, Error> {
+        let index = *self
+            .words
+            .get(&self.key(name))
+            .ok_or(ForthError::UnknownWord(None))?;
+
+        self.definitions.truncate(index);
+        self.definition_names.truncate(index);
+        self.definition_spans.truncate(index);
+        self.stack_effects.truncate(index);
+        self.values.retain(|&value| value < index);
+        self.markers.retain(|&marker| marker < index);
+
+        let mut forgotten = HashSet::new();
+        self.words.retain(|name, &mut definition| {
+            let kept = definition < index;
+            if !kept && let WordType::UserDefined(name) = name {
+                forgotten.insert(name.to_string());
+            }
+            kept
+        });
+        for (definition, name) in self.definition_names.iter().enumerate() {
+            let name = self.case_sensitivity.fold(name);
+            if forgotten.contains(&name) {
+                self.words.insert(WordType::UserDefined(name), definition);
+            }
+        }
+        Ok(index)
+    }
+
+    /// Gets the number of definitions made so far, including the ones that were redefined.
+    pub fn definition_count(&self) -> usize {
+        self.definitions.len()
+    }
+
     /// Checks if a word is defined in the Forth interpreter.
     pub fn is_word_defined(&self, name: &WordType) -> bool {
-        self.words.contains_key(name)
+        self.words.contains_key(&self.key(name))
     }
 
     /// Gets the definition of a word in the Forth interpreter.
@@ -351,12 +910,75 @@ impl WordDefinitionManager {
     /// If the word is not defined, it returns `None`.
     pub fn get_word_definition(&self, name: &WordType) -> Option<&Vec<WordData>> {
         self.words
-            .get(name)
+            .get(&self.key(name))
             .and_then(|&index| self.definitions.get(index))
     }
 
+    /// Gets the stack effect of a word, written in the comment that follows its name
+    /// (`: double ( n -- 2n ) dup + ;`), without the parentheses.
+    /// If the word is not defined or has no stack effect comment, it returns `None`.
+    pub fn stack_effect(&self, name: &WordType) -> Option<&str> {
+        self.words
+            .get(&self.key(name))
+            .and_then(|&index| self.stack_effects.get(index))
+            .and_then(Option::as_deref)
+    }
+
+    /// Gets the names of the user-defined words that can be used, from the newest to the oldest,
+    /// spelled as in their definition. Redefined words are listed once.
+    pub fn word_names(&self) -> Vec<&str> {
+        let mut indexes: Vec<usize> = self
+            .words
+            .iter()
+            .filter(|(name, _)| matches!(name, WordType::UserDefined(_)))
+            .map(|(_, &index)| index)
+            .collect();
+        indexes.sort_unstable_by(|a, b| b.cmp(a));
+        indexes
+            .into_iter()
+            .filter_map(|index| self.definition_names.get(index))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Gets the source of the current definition of a word, as shown by `SEE`:
+    /// `: Name ( stack effect ) body ;`, with the name and the body spelled as they were written.
+    /// Words defined without their source, as the ones created by `VARIABLE`, `CONSTANT`,
+    /// `VALUE` and `CREATE`, are shown with the numbers they push.
+    /// If the word is not defined, it returns `None`.
+    pub fn source(&self, name: &WordType) -> Option<String> {
+        let index = *self.words.get(&self.key(name))?;
+        let mut source = vec![
+            ":".to_string(),
+            self.definition_names.get(index)?.to_string(),
+        ];
+        if let Some(Some(stack_effect)) = self.stack_effects.get(index) {
+            source.push(format!("( {} )", stack_effect));
+        }
+
+        let spans = self.definition_spans.get(index)?;
+        if spans.is_empty() {
+            let definition = self.definitions.get(index)?;
+            source.extend(definition.iter().filter_map(|data| match data {
+                WordData::Number(number) => Some(number.to_string()),
+                WordData::MemoryWord(MemoryOperation::Fetch) => Some("@".to_string()),
+                _ => None,
+            }));
+        } else {
+            let mut previous = None;
+            for span in spans {
+                if previous != Some(span) {
+                    source.push(span.word.to_string());
+                }
+                previous = Some(span);
+            }
+        }
+        source.push(";".to_string());
+        Some(source.join(" "))
+    }
+
     fn is_word_name_valid(&self, name: &str) -> bool {
-        if name.parse::<i16>().is_ok() {
+        if name.is_empty() || name.parse::<Cell>().is_ok() {
             return false;
         }
 
@@ -365,6 +987,70 @@ impl WordDefinitionManager {
     }
 }
 
+/// Checks if a loop index moving from `previous` to `current` crosses the boundary
+/// between `limit - 1` and `limit`, which is the exit condition of `LOOP` and `+LOOP`.
+/// The distances to the limit wrap around according to the width of the cells.
+fn crosses_loop_limit(previous: Cell, current: Cell, limit: Cell, cell_size: CellSize) -> bool {
+    let distance = |index: Cell| cell_size.wrap(index as i128 - limit as i128);
+    (distance(previous) ^ distance(current)) < 0
+}
+
+/// Checks that every control structure of a definition is properly closed
+/// (IF ... ELSE ... THEN, DO ... LOOP, BEGIN ... UNTIL, BEGIN ... WHILE ... REPEAT, BEGIN ... AGAIN),
+/// so that an unbalanced word is rejected when it is defined instead of when it runs.
+fn check_control_structures(definition: &[WordData]) -> Result<(), Error> {
+    let mut open_structures: Vec<&DefinitionType> = Vec::new();
+
+    for instruction in definition {
+        let WordData::DefinitionType(definition_type) = instruction else {
+            continue;
+        };
+
+        match *definition_type {
+            IF | DO | BEGIN => open_structures.push(definition_type),
+            ELSE => {
+                close_structure(&mut open_structures, &[IF])?;
+                open_structures.push(definition_type);
+            }
+            THEN => close_structure(&mut open_structures, &[IF, ELSE])?,
+            LOOP | PLUS_LOOP => close_structure(&mut open_structures, &[DO])?,
+            UNTIL | AGAIN => close_structure(&mut open_structures, &[BEGIN])?,
+            WHILE => {
+                close_structure(&mut open_structures, &[BEGIN])?;
+                open_structures.push(definition_type);
+            }
+            REPEAT => close_structure(&mut open_structures, &[WHILE])?,
+            _ => {}
+        }
+    }
+
+    if !open_structures.is_empty() {
+        return Err(ForthError::UnbalancedControlStructure.into());
+    }
+    Ok(())
+}
+
+/// Closes the innermost open control structure if it is one of the `expected` ones.
+fn close_structure(
+    open_structures: &mut Vec<&DefinitionType>,
+    expected: &[DefinitionType],
+) -> Result<(), Error> {
+    match open_structures.pop() {
+        Some(open) if expected.contains(open) => Ok(()),
+        _ => Err(ForthError::UnbalancedControlStructure.into()),
+    }
+}
+
+/// Finds the stack effect comment in the body of a word definition.
+fn find_stack_effect(body: &[Instruction]) -> Option<String> {
+    body.iter()
+        .take_while(|element| **element != Instruction::EndDefinition)
+        .find_map(|element| match element {
+            Instruction::StackEffect(text) => Some(text.to_string()),
+            _ => None,
+        })
+}
+
 /// Finds the end of a word definition in the body of instructions.
 fn find_end_definition(body: &[Instruction]) -> Option<usize> {
     for (index, element) in body.iter().enumerate() {
@@ -378,8 +1064,12 @@ fn find_end_definition(body: &[Instruction]) -> Option<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::forth::boolean_operations::EQUAL;
+    use crate::forth::boolean_operations::{EQUAL, FORTH_TRUE, GREATER_THAN};
+    use crate::forth::dictionary_word::WORDS;
     use crate::forth::intruction::Instruction;
+    use crate::forth::output_instructions::EMIT;
+    use crate::stack::stack_errors::StackError;
+    use crate::stack::stack_operations::{DUP, R_FROM, TO_R};
     use std::io::Sink;
 
     #[test]
@@ -434,7 +1124,18 @@ mod tests {
 
         let result = word_manager.run_word::<Sink>(&mut handler, "ABS");
 
-        assert_eq!(result, Err(ForthError::UnknownWord.into()));
+        assert_eq!(result, Err(ForthError::UnknownWord(None).into()));
+    }
+
+    #[test]
+    fn cannot_define_a_word_without_a_name() {
+        let mut word_manager = WordDefinitionManager::new();
+        let word = vec![Instruction::number(1), Instruction::end_definition()];
+
+        let result = word_manager.define_new_word(WordType::UserDefined(String::new()), word);
+
+        assert_eq!(result, Err(ForthError::InvalidWord.into()));
+        assert_eq!(word_manager.definition_count(), 0);
     }
 
     #[test]
@@ -569,4 +1270,583 @@ mod tests {
 
         assert_eq!(result, &expected_result);
     }
+
+    #[test]
+    fn can_execute_word_that_contains_counted_loop() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::number(5),
+            Instruction::number(0),
+            Instruction::definition_type(DO),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::definition_type(LOOP),
+            Instruction::end_definition(),
+        ];
+        let expected_result = vec![0, 1, 2, 3, 4];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("count".to_string()), word);
+        let _ = word_manager.run_word(&mut handler, "count");
+        let result = handler.handle_get_stack_content();
+
+        assert_eq!(result, &expected_result);
+    }
+
+    #[test]
+    fn can_execute_nested_loops_with_outer_index() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::number(3),
+            Instruction::number(1),
+            Instruction::definition_type(DO),
+            Instruction::number(3),
+            Instruction::number(1),
+            Instruction::definition_type(DO),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::definition_type(OUTER_LOOP_INDEX),
+            Instruction::operator("*".to_string()),
+            Instruction::definition_type(LOOP),
+            Instruction::definition_type(LOOP),
+            Instruction::end_definition(),
+        ];
+        let expected_result = vec![1, 2, 2, 4];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("table".to_string()), word);
+        let _ = word_manager.run_word(&mut handler, "table");
+        let result = handler.handle_get_stack_content();
+
+        assert_eq!(result, &expected_result);
+    }
+
+    #[test]
+    fn can_execute_loop_with_negative_step() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::number(0),
+            Instruction::number(6),
+            Instruction::definition_type(DO),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::number(-2),
+            Instruction::definition_type(PLUS_LOOP),
+            Instruction::end_definition(),
+        ];
+        let expected_result = vec![6, 4, 2, 0];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("down".to_string()), word);
+        let _ = word_manager.run_word(&mut handler, "down");
+        let result = handler.handle_get_stack_content();
+
+        assert_eq!(result, &expected_result);
+    }
+
+    #[test]
+    fn leave_exits_the_innermost_loop() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::number(10),
+            Instruction::number(0),
+            Instruction::definition_type(DO),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::number(2),
+            Instruction::logical_operation(EQUAL),
+            Instruction::definition_type(IF),
+            Instruction::definition_type(LEAVE),
+            Instruction::definition_type(THEN),
+            Instruction::definition_type(LOOP),
+            Instruction::number(99),
+            Instruction::end_definition(),
+        ];
+        let expected_result = vec![0, 1, 2, 99];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("first".to_string()), word);
+        let _ = word_manager.run_word(&mut handler, "first");
+        let result = handler.handle_get_stack_content();
+
+        assert_eq!(result, &expected_result);
+    }
+
+    #[test]
+    fn unloop_and_exit_return_from_the_word() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::number(10),
+            Instruction::number(0),
+            Instruction::definition_type(DO),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::number(1),
+            Instruction::logical_operation(EQUAL),
+            Instruction::definition_type(IF),
+            Instruction::definition_type(UNLOOP),
+            Instruction::definition_type(EXIT),
+            Instruction::definition_type(THEN),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::definition_type(LOOP),
+            Instruction::number(99),
+            Instruction::end_definition(),
+        ];
+        let expected_result = vec![0];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("early".to_string()), word);
+        let _ = word_manager.run_word(&mut handler, "early");
+        let result = handler.handle_get_stack_content();
+
+        assert_eq!(result, &expected_result);
+    }
+
+    #[test]
+    fn loop_without_bounds_should_give_underflow_error() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::definition_type(DO),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::definition_type(LOOP),
+            Instruction::end_definition(),
+        ];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("broken".to_string()), word);
+        let _ = handler.handle_push_element(1);
+        let result = word_manager.run_word(&mut handler, "broken");
+
+        assert_eq!(result, Err(StackError::Underflow.into()));
+    }
+
+    #[test]
+    fn loop_index_outside_loop_should_give_return_stack_underflow_error() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::end_definition(),
+        ];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("index".to_string()), word);
+        let result = word_manager.run_word(&mut handler, "index");
+
+        assert_eq!(result, Err(StackError::ReturnStackUnderflow.into()));
+    }
+
+    #[test]
+    fn can_execute_begin_until_loop() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::definition_type(BEGIN),
+            Instruction::number(10),
+            Instruction::operator("/".to_string()),
+            Instruction::stack_word(DUP),
+            Instruction::number(0),
+            Instruction::logical_operation(EQUAL),
+            Instruction::definition_type(UNTIL),
+            Instruction::end_definition(),
+        ];
+        let expected_result = vec![0];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("digits".to_string()), word);
+        let _ = handler.handle_push_element(12345);
+        let _ = word_manager.run_word(&mut handler, "digits");
+        let result = handler.handle_get_stack_content();
+
+        assert_eq!(result, &expected_result);
+    }
+
+    #[test]
+    fn can_execute_begin_while_repeat_loop() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::definition_type(BEGIN),
+            Instruction::stack_word(DUP),
+            Instruction::number(0),
+            Instruction::logical_operation(GREATER_THAN),
+            Instruction::definition_type(WHILE),
+            Instruction::stack_word(DUP),
+            Instruction::number(1),
+            Instruction::operator("-".to_string()),
+            Instruction::definition_type(REPEAT),
+            Instruction::end_definition(),
+        ];
+        let expected_result = vec![3, 2, 1, 0];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("down".to_string()), word);
+        let _ = handler.handle_push_element(3);
+        let _ = word_manager.run_word(&mut handler, "down");
+        let result = handler.handle_get_stack_content();
+
+        assert_eq!(result, &expected_result);
+    }
+
+    #[test]
+    fn can_exit_from_begin_again_loop() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::number(0),
+            Instruction::definition_type(BEGIN),
+            Instruction::number(1),
+            Instruction::operator("+".to_string()),
+            Instruction::stack_word(DUP),
+            Instruction::number(5),
+            Instruction::logical_operation(EQUAL),
+            Instruction::definition_type(IF),
+            Instruction::definition_type(EXIT),
+            Instruction::definition_type(THEN),
+            Instruction::definition_type(AGAIN),
+            Instruction::end_definition(),
+        ];
+        let expected_result = vec![5];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("five".to_string()), word);
+        let _ = word_manager.run_word(&mut handler, "five");
+        let result = handler.handle_get_stack_content();
+
+        assert_eq!(result, &expected_result);
+    }
+
+    #[test]
+    fn cannot_define_word_with_unbalanced_loops() {
+        let mut word_manager = WordDefinitionManager::new();
+        let unclosed_begin: Vec<Instruction> = vec![
+            Instruction::definition_type(BEGIN),
+            Instruction::number(1),
+            Instruction::end_definition(),
+        ];
+        let while_without_repeat: Vec<Instruction> = vec![
+            Instruction::definition_type(BEGIN),
+            Instruction::definition_type(WHILE),
+            Instruction::definition_type(UNTIL),
+            Instruction::end_definition(),
+        ];
+        let crossed_structures: Vec<Instruction> = vec![
+            Instruction::definition_type(IF),
+            Instruction::definition_type(BEGIN),
+            Instruction::definition_type(THEN),
+            Instruction::definition_type(UNTIL),
+            Instruction::end_definition(),
+        ];
+        let expected_result = Err(ForthError::UnbalancedControlStructure.into());
+
+        for word in [unclosed_begin, while_without_repeat, crossed_structures] {
+            let result =
+                word_manager.define_new_word(WordType::UserDefined("bad".to_string()), word);
+            assert_eq!(result, expected_result);
+        }
+        assert!(!word_manager.is_word_defined(&WordType::UserDefined("bad".to_string())));
+    }
+
+    #[test]
+    fn loop_index_is_read_from_the_return_stack() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let word: Vec<Instruction> = vec![
+            Instruction::number(2),
+            Instruction::number(0),
+            Instruction::definition_type(DO),
+            Instruction::number(7),
+            Instruction::stack_word(TO_R),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::stack_word(R_FROM),
+            Instruction::definition_type(LOOP_INDEX),
+            Instruction::definition_type(LOOP),
+            Instruction::end_definition(),
+        ];
+        let expected_result = vec![7, 7, 0, 7, 7, 1];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("peek".to_string()), word);
+        let _ = word_manager.run_word(&mut handler, "peek");
+
+        assert_eq!(handler.handle_get_stack_content(), &expected_result);
+        assert_eq!(handler.handle_return_stack_size(), 0);
+    }
+
+    #[test]
+    fn errors_raised_inside_nested_words_have_a_traceback() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let inner = vec![Instruction::stack_word(DUP), Instruction::end_definition()];
+        let outer = vec![
+            Instruction::definition_type(DefinitionType::name("inner")),
+            Instruction::end_definition(),
+        ];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("inner".to_string()), inner);
+        let _ = word_manager.define_new_word(WordType::UserDefined("outer".to_string()), outer);
+        let result = word_manager.run_word(&mut handler, "outer");
+
+        assert_eq!(result, Err(StackError::Underflow.into()));
+        assert_eq!(
+            word_manager.take_traceback(),
+            vec!["inner".to_string(), "outer".to_string()]
+        );
+        assert!(word_manager.take_traceback().is_empty());
+        assert!(word_manager.execution_stack.is_empty());
+    }
+
+    #[test]
+    fn anonymous_definitions_are_discarded_once_executed() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let body = vec![
+            Instruction::number(-1),
+            Instruction::definition_type(IF),
+            Instruction::number(10),
+            Instruction::definition_type(ELSE),
+            Instruction::number(20),
+            Instruction::definition_type(THEN),
+            Instruction::end_definition(),
+        ];
+
+        let result = word_manager.run_anonymous(&mut handler, body, Vec::new());
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(handler.handle_get_stack_content(), &vec![10]);
+        assert!(word_manager.definitions.is_empty());
+    }
+
+    #[test]
+    fn dictionary_words_can_not_be_compiled() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let body = vec![
+            Instruction::number(-1),
+            Instruction::definition_type(IF),
+            Instruction::dictionary_word(WORDS, ""),
+            Instruction::definition_type(THEN),
+            Instruction::end_definition(),
+        ];
+
+        let result = word_manager.run_anonymous(&mut handler, body, Vec::new());
+
+        assert_eq!(result, Err(ForthError::InterpretOnlyWord.into()));
+        assert!(word_manager.definitions.is_empty());
+    }
+
+    #[test]
+    fn recurse_can_not_be_used_in_anonymous_definitions() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let body = vec![
+            Instruction::number(-1),
+            Instruction::definition_type(IF),
+            Instruction::definition_type(DefinitionType::Recurse),
+            Instruction::definition_type(THEN),
+            Instruction::end_definition(),
+        ];
+
+        let result = word_manager.run_anonymous(&mut handler, body, Vec::new());
+
+        assert_eq!(result, Err(ForthError::CompileOnlyWord.into()));
+        assert!(word_manager.definitions.is_empty());
+    }
+
+    #[test]
+    fn the_own_name_calls_the_previous_definition_if_there_is_one() {
+        let mut word_manager = WordDefinitionManager::new();
+        let body = || {
+            vec![
+                Instruction::definition_type(DefinitionType::name("count-down")),
+                Instruction::end_definition(),
+            ]
+        };
+        let name = || WordType::UserDefined("count-down".to_string());
+
+        let _ = word_manager.define_new_word(name(), body());
+        assert_eq!(
+            word_manager.get_word_definition(&name()),
+            Some(&vec![WordData::DefinitionIndex(0)])
+        );
+        let _ = word_manager.define_new_word(name(), body());
+
+        assert_eq!(
+            word_manager.get_word_definition(&name()),
+            Some(&vec![WordData::DefinitionIndex(0)])
+        );
+    }
+
+    #[test]
+    fn undefined_names_are_kept_to_be_reported() {
+        let mut word_manager = WordDefinitionManager::new();
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let body = vec![
+            Instruction::number(1),
+            Instruction::definition_type(DefinitionType::name("dupp")),
+            Instruction::end_definition(),
+        ];
+        let anonymous_body = vec![
+            Instruction::number(-1),
+            Instruction::definition_type(IF),
+            Instruction::definition_type(DefinitionType::name("dupp")),
+            Instruction::definition_type(THEN),
+            Instruction::end_definition(),
+        ];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("typo".to_string()), body);
+        let undefined_names = word_manager.take_undefined_names();
+        let result = word_manager.run_anonymous(&mut handler, anonymous_body, Vec::new());
+
+        assert_eq!(undefined_names, vec!["dupp"]);
+        assert_eq!(
+            word_manager.get_word_definition(&WordType::UserDefined("typo".to_string())),
+            Some(&vec![WordData::number(1)])
+        );
+        assert_eq!(result, Err(ForthError::UnknownWord(None).into()));
+    }
+
+    #[test]
+    fn the_stack_effect_is_kept_with_the_definition() {
+        let mut word_manager = WordDefinitionManager::new();
+        let word = vec![
+            Instruction::stack_effect("n -- 2n"),
+            Instruction::stack_word(DUP),
+            Instruction::operator("+"),
+            Instruction::end_definition(),
+        ];
+        let name = WordType::UserDefined("double".to_string());
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("double".to_string()), word);
+
+        assert_eq!(word_manager.stack_effect(&name), Some("n -- 2n"));
+        assert_eq!(
+            word_manager.get_word_definition(&name),
+            Some(&vec![
+                WordData::stack_word(DUP),
+                WordData::operator("+".to_string())
+            ])
+        );
+    }
+
+    #[test]
+    fn names_are_case_insensitive_by_default_and_keep_their_spelling() {
+        let mut word_manager = WordDefinitionManager::new();
+        let word = vec![Instruction::number(1), Instruction::end_definition()];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("OneMore".to_string()), word);
+
+        assert!(word_manager.is_word_defined(&WordType::UserDefined("onemore".to_string())));
+        assert!(word_manager.is_word_defined(&WordType::UserDefined("ONEMORE".to_string())));
+        assert_eq!(word_manager.word_names(), vec!["OneMore"]);
+    }
+
+    #[test]
+    fn case_sensitive_names_only_match_the_same_spelling() {
+        let mut word_manager =
+            WordDefinitionManager::new().with_case_sensitivity(CaseSensitivity::Sensitive);
+        let word = vec![Instruction::number(1), Instruction::end_definition()];
+        let other_word = vec![Instruction::number(2), Instruction::end_definition()];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("Word".to_string()), word);
+        let _ = word_manager.define_new_word(WordType::UserDefined("WORD".to_string()), other_word);
+
+        assert!(!word_manager.is_word_defined(&WordType::UserDefined("word".to_string())));
+        assert_eq!(
+            word_manager.get_word_definition(&WordType::UserDefined("Word".to_string())),
+            Some(&vec![WordData::number(1)])
+        );
+        assert_eq!(word_manager.word_names(), vec!["WORD", "Word"]);
+    }
+
+    #[test]
+    fn redefined_words_are_listed_once_with_their_last_spelling() {
+        let mut word_manager = WordDefinitionManager::new();
+        let word = vec![Instruction::number(1), Instruction::end_definition()];
+        let redefinition = vec![Instruction::number(2), Instruction::end_definition()];
+        let other_word = vec![Instruction::number(3), Instruction::end_definition()];
+
+        let _ = word_manager.define_new_word(WordType::UserDefined("first".to_string()), word);
+        let _ =
+            word_manager.define_new_word(WordType::UserDefined("second".to_string()), other_word);
+        let _ =
+            word_manager.define_new_word(WordType::UserDefined("FIRST".to_string()), redefinition);
+
+        assert_eq!(word_manager.word_names(), vec!["FIRST", "second"]);
+    }
+
+    #[test]
+    fn the_source_is_shown_as_it_was_written() {
+        let mut word_manager = WordDefinitionManager::new();
+        let word = vec![
+            Instruction::stack_effect("n -- n*n"),
+            Instruction::stack_word(DUP),
+            Instruction::operator("*"),
+            Instruction::end_definition(),
+        ];
+        let spans = vec![
+            Span::new(1, 10, "( n -- n*n )"),
+            Span::new(1, 23, "DUP"),
+            Span::new(1, 27, "*"),
+            Span::new(1, 29, ";"),
+        ];
+
+        let _ = word_manager.define_new_word_with_spans(
+            WordType::UserDefined("Square".to_string()),
+            word,
+            spans,
+        );
+        let _ = word_manager.define_value(WordType::UserDefined("Limit".to_string()), 8);
+
+        assert_eq!(
+            word_manager.source(&WordType::UserDefined("square".to_string())),
+            Some(": Square ( n -- n*n ) DUP * ;".to_string())
+        );
+        assert_eq!(
+            word_manager.source(&WordType::UserDefined("limit".to_string())),
+            Some(": Limit 8 @ ;".to_string())
+        );
+        assert_eq!(
+            word_manager.source(&WordType::UserDefined("cube".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn forget_removes_the_word_and_the_ones_defined_after_it() {
+        let mut word_manager = WordDefinitionManager::new();
+        let body = |number| vec![Instruction::number(number), Instruction::end_definition()];
+        let name = |name: &str| WordType::UserDefined(name.to_string());
+
+        let _ = word_manager.define_new_word(name("foo"), body(1));
+        let _ = word_manager.define_new_word(name("bar"), body(2));
+        let _ = word_manager.define_new_word(name("FOO"), body(3));
+        let _ = word_manager.define_new_word(name("baz"), body(4));
+        let result = word_manager.forget(&name("foo"));
+
+        assert_eq!(result, Ok(2));
+        assert_eq!(
+            word_manager.get_word_definition(&name("foo")),
+            Some(&vec![WordData::number(1)])
+        );
+        assert!(word_manager.is_word_defined(&name("bar")));
+        assert!(!word_manager.is_word_defined(&name("baz")));
+        assert_eq!(word_manager.word_names(), vec!["bar", "foo"]);
+    }
+
+    #[test]
+    fn cannot_forget_an_unknown_word() {
+        let mut word_manager = WordDefinitionManager::new();
+
+        let result = word_manager.forget(&WordType::UserDefined("ghost".to_string()));
+
+        assert_eq!(result, Err(ForthError::UnknownWord(None).into()));
+    }
+
+    #[test]
+    fn markers_can_not_be_used_inside_definitions() {
+        let mut word_manager = WordDefinitionManager::new();
+        let marker = WordType::UserDefined("-work-".to_string());
+        let word = vec![
+            Instruction::definition_type(DefinitionType::name("-work-")),
+            Instruction::end_definition(),
+        ];
+
+        let _ = word_manager.define_marker(WordType::UserDefined("-work-".to_string()));
+        let result = word_manager.define_new_word(WordType::UserDefined("reset".to_string()), word);
+
+        assert!(word_manager.is_marker(&marker));
+        assert_eq!(result, Err(ForthError::InvalidWord.into()));
+    }
 }
@@ -1,4 +1,9 @@
-use crate::{BooleanOperation, LogicalOperation, stack::stack_operations::StackOperation};
+use crate::{
+    BooleanOperation, LogicalOperation,
+    cell::Cell,
+    memory::{memory_operations::MemoryOperation, string_operations::StringOperation},
+    stack::stack_operations::StackOperation,
+};
 
 use super::{definition_type::DefinitionType, output_instructions::OutputInstruction};
 
@@ -7,7 +12,7 @@ use super::{definition_type::DefinitionType, output_instructions::OutputInstruct
 /// Additionally, it includes types for defining new words and logical operations
 #[derive(Debug, PartialEq)]
 pub enum WordData {
-    Number(i16),
+    Number(Cell),
     Operator(String),
     StackWord(StackOperation),
     DefinitionType(DefinitionType),
@@ -15,10 +20,12 @@ pub enum WordData {
     LogicalOperation(LogicalOperation),
     Output(OutputInstruction),
     DefinitionIndex(usize),
+    MemoryWord(MemoryOperation),
+    StringWord(StringOperation),
 }
 
 impl WordData {
-    pub fn number(value: i16) -> Self {
+    pub fn number(value: Cell) -> Self {
         WordData::Number(value)
     }
 
@@ -45,4 +52,12 @@ impl WordData {
     pub fn output(output: OutputInstruction) -> Self {
         WordData::Output(output)
     }
+
+    pub fn memory_word(op: MemoryOperation) -> Self {
+        WordData::MemoryWord(op)
+    }
+
+    pub fn string_word(op: StringOperation) -> Self {
+        WordData::StringWord(op)
+    }
 }
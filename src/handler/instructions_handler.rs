@@ -2,13 +2,24 @@ use std::io::Write;
 
 use crate::{
     BooleanOperation, Instruction, LogicalOperation, Stack,
-    calculator::operations::Calculator,
+    calculator::{
+        arithmetic_policy::ArithmeticPolicy, calculator_errors::CalculatorError,
+        division_mode::DivisionMode, operations::Calculator,
+    },
+    cell::{Cell, CellSize, DECIMAL_BASE, format_in_base, numeric_base},
     errors::Error,
     forth::{
-        boolean_operations::BooleanOperationManager,
-        output_instructions::{CR, DOT, EMIT, OutputInstruction},
+        boolean_operations::{BooleanMode, BooleanOperationManager},
+        forth_errors::ForthError,
+        output_instructions::{BLANK, DUMP_LINE_CELLS, OutputInstruction, OutputMode},
+        pictured_output::PicturedOutput,
         word_data::WordData,
     },
+    memory::{
+        core::DataSpace,
+        memory_operations::{CHAR_MASK, execute_memory_operation},
+        string_operations::{StringKind, execute_string_operation},
+    },
     stack::stack_operations::execute_stack_operation,
 };
 
@@ -19,13 +30,22 @@ use crate::{
 /// ## Fields
 ///
 /// - `stack`: The stack used to store the values.
+/// - `return_stack`: The return stack used for loop parameters and `>R`/`R>` transfers.
+/// - `data_space`: The data space where variables and buffers are stored.
 /// - `calculator`: The calculator used to perform arithmetic operations.
 /// - `boolean_manager`: The boolean manager used to manage the boolean operations.
+/// - `pictured`: The string being built by the pictured numeric output words.
+/// - `output_mode`: The spacing of the output of `EMIT` and `."`.
 /// - `writer`: The writer used to write the output.
 ///
 /// ## Principal Methods
 ///
 /// - `new`: Creates a new instance of the ExecutionHandler.
+/// - `with_cell_size`: Sets the width of the cells.
+/// - `with_arithmetic_policy`: Sets the policy for arithmetic results that do not fit in a cell.
+/// - `with_division_mode`: Sets the rounding of the quotients.
+/// - `with_boolean_mode`: Sets the semantics of `AND`, `OR` and `XOR`.
+/// - `with_output_mode`: Sets the spacing of the output of `EMIT` and `."`.
 /// - `handle_instruction`: Handles the instructions of the Forth interpreter.
 /// - `handle_word_instruction`: Handles the word instructions.
 /// - `handle_get_writer`: Returns a mutable reference to the writer.
@@ -35,10 +55,22 @@ use crate::{
 /// - `handle_get_stack_content`: Returns a reference to the stack content.
 /// - `handle_is_empty`: Checks if the stack is empty.
 /// - `handle_stack_size`: Returns the size of the stack.
+/// - `handle_push_return_element`: Pushes an element onto the return stack.
+/// - `handle_drop_return_element`: Drops the top element from the return stack.
+/// - `handle_get_return_element`: Returns an element of the return stack by its depth.
+/// - `handle_allot`: Reserves cells in the data space.
+/// - `handle_store`: Stores a value in the data space.
+/// - `handle_base`: Returns the numeric base used to read and print numbers.
+/// - `handle_store_string`: Stores a string in the data space.
+/// - `handle_transient_string`: Stores a string in the transient region of the data space.
 pub struct ExecutionHandler<W: Write> {
     stack: Stack,
+    return_stack: Stack,
+    data_space: DataSpace,
     calculator: Calculator,
     boolean_manager: BooleanOperationManager,
+    pictured: PicturedOutput,
+    output_mode: OutputMode,
     writer: Option<W>,
 }
 
@@ -52,12 +84,71 @@ impl<W: Write> ExecutionHandler<W> {
     pub fn new(stack_capacity: Option<usize>, writer: Option<W>) -> Self {
         ExecutionHandler {
             stack: Stack::new(stack_capacity),
+            return_stack: Stack::new_return_stack(None),
+            data_space: DataSpace::new(None),
             calculator: Calculator::new(),
             boolean_manager: BooleanOperationManager::new(),
+            pictured: PicturedOutput::new(),
+            output_mode: OutputMode::default(),
             writer,
         }
     }
 
+    /// Sets the size of the data space, in bytes.
+    /// If not provided, the default size is used.
+    pub fn with_data_space_size(mut self, size: Option<usize>) -> Self {
+        self.data_space = DataSpace::new(size).with_cell_size(self.data_space.cell_size());
+        self
+    }
+
+    /// Sets the width of the cells of the stacks, the data space and the calculator.
+    /// The sizes in bytes of the stacks and the data space are kept.
+    pub fn with_cell_size(mut self, cell_size: CellSize) -> Self {
+        self.stack = self.stack.with_cell_size(cell_size);
+        self.return_stack = self.return_stack.with_cell_size(cell_size);
+        self.data_space = self.data_space.with_cell_size(cell_size);
+        self.calculator = self.calculator.with_cell_size(cell_size);
+        self.boolean_manager = self.boolean_manager.with_cell_size(cell_size);
+        self
+    }
+
+    /// Sets the policy applied by the calculator to the results that do not fit in a cell.
+    pub fn with_arithmetic_policy(mut self, policy: ArithmeticPolicy) -> Self {
+        self.calculator = self.calculator.with_arithmetic_policy(policy);
+        self
+    }
+
+    /// Sets the rounding of the quotients computed by the calculator.
+    pub fn with_division_mode(mut self, division: DivisionMode) -> Self {
+        self.calculator = self.calculator.with_division_mode(division);
+        self
+    }
+
+    /// Sets the semantics of `AND`, `OR` and `XOR`: bitwise or logical.
+    pub fn with_boolean_mode(mut self, mode: BooleanMode) -> Self {
+        self.boolean_manager = self.boolean_manager.with_boolean_mode(mode);
+        self
+    }
+
+    /// Sets the spacing of the output of `EMIT` and `."`: legacy or standard.
+    pub fn with_output_mode(mut self, output_mode: OutputMode) -> Self {
+        self.output_mode = output_mode;
+        self
+    }
+
+    /// Returns the width of the cells.
+    pub fn handle_cell_size(&self) -> CellSize {
+        self.data_space.cell_size()
+    }
+
+    /// Returns the numeric base used to read and print numbers, held by `BASE`.
+    /// If `BASE` holds a value that is not between 2 and 36, numbers are decimal.
+    pub fn handle_base(&self) -> u32 {
+        self.data_space
+            .fetch(self.data_space.base_address())
+            .map_or(DECIMAL_BASE, numeric_base)
+    }
+
     /// Handles the instructions of the Forth interpreter.
     ///
     /// In this method, the instructions are processed one by one.
@@ -67,7 +158,7 @@ impl<W: Write> ExecutionHandler<W> {
             &Instruction::Number(number) => self.handle_push_element(number)?,
             Instruction::Operator(operator) => self.handle_calculate(operator)?,
             Instruction::StackWord(stack_word) => {
-                execute_stack_operation(&mut self.stack, stack_word)?
+                execute_stack_operation(&mut self.stack, &mut self.return_stack, stack_word)?
             }
             Instruction::BooleanOperation(boolean_operation) => {
                 self.handle_boolean_operation(boolean_operation)?
@@ -75,6 +166,17 @@ impl<W: Write> ExecutionHandler<W> {
             Instruction::LogicalOperation(logical_operation) => {
                 self.handle_logical_operation(logical_operation)?
             }
+            Instruction::MemoryWord(memory_word) => {
+                execute_memory_operation(&mut self.stack, &mut self.data_space, memory_word)?
+            }
+            Instruction::StringWord(string_word) => {
+                execute_string_operation(&mut self.stack, &self.data_space, string_word)?
+            }
+            Instruction::StringLiteral(kind, text) => {
+                for value in self.handle_transient_string(*kind, text)? {
+                    self.stack.push(value)?;
+                }
+            }
             _ => self.handle_generation_output(instruction)?,
         }
         Ok(())
@@ -89,7 +191,7 @@ impl<W: Write> ExecutionHandler<W> {
             &WordData::Number(number) => self.handle_push_element(number)?,
             WordData::Operator(operator) => self.handle_calculate(operator)?,
             WordData::StackWord(stack_word) => {
-                execute_stack_operation(&mut self.stack, stack_word)?
+                execute_stack_operation(&mut self.stack, &mut self.return_stack, stack_word)?
             }
             WordData::BooleanOperation(boolean_operation) => {
                 self.handle_boolean_operation(boolean_operation)?
@@ -97,12 +199,13 @@ impl<W: Write> ExecutionHandler<W> {
             WordData::LogicalOperation(logical_operation) => {
                 self.handle_logical_operation(logical_operation)?
             }
-            WordData::Output(DOT) => self.handle_output_dot()?,
-            WordData::Output(CR) => self.handle_output_cr()?,
-            WordData::Output(EMIT) => self.handle_output_emit()?,
-            WordData::Output(OutputInstruction::DotQuote(str)) => {
-                self.handle_output_dot_quote(str)?
+            WordData::MemoryWord(memory_word) => {
+                execute_memory_operation(&mut self.stack, &mut self.data_space, memory_word)?
             }
+            WordData::StringWord(string_word) => {
+                execute_string_operation(&mut self.stack, &self.data_space, string_word)?
+            }
+            WordData::Output(output) => self.handle_output(output)?,
             _ => {}
         }
         Ok(())
@@ -115,25 +218,25 @@ impl<W: Write> ExecutionHandler<W> {
 
     /// Returns a reference to the top element of the stack.
     /// If the stack is empty, it returns an error.
-    pub fn handle_get_top_element(&mut self) -> Result<&i16, Error> {
+    pub fn handle_get_top_element(&mut self) -> Result<&Cell, Error> {
         self.stack.top()
     }
 
     /// Pushes an element onto the stack.
     /// If the stack is full, it returns an error.
-    pub fn handle_push_element(&mut self, element: i16) -> Result<(), Error> {
+    pub fn handle_push_element(&mut self, element: Cell) -> Result<(), Error> {
         self.stack.push(element)?;
         Ok(())
     }
 
     /// Drops the top element from the stack.
     /// If the stack is empty, it returns an error.
-    pub fn handle_drop_element(&mut self) -> Result<i16, Error> {
+    pub fn handle_drop_element(&mut self) -> Result<Cell, Error> {
         self.stack.drop()
     }
 
     /// Returns a reference to the stack content.
-    pub fn handle_get_stack_content(&self) -> &Vec<i16> {
+    pub fn handle_get_stack_content(&self) -> &Vec<Cell> {
         self.stack.get_stack_content()
     }
 
@@ -147,22 +250,121 @@ impl<W: Write> ExecutionHandler<W> {
         self.stack.size()
     }
 
+    /// Pushes an element onto the return stack.
+    /// If the return stack is full, it returns an error.
+    pub fn handle_push_return_element(&mut self, element: Cell) -> Result<(), Error> {
+        self.return_stack.push(element)
+    }
+
+    /// Drops the top element from the return stack.
+    /// If the return stack is empty, it returns an error.
+    pub fn handle_drop_return_element(&mut self) -> Result<Cell, Error> {
+        self.return_stack.drop()
+    }
+
+    /// Returns a reference to the element of the return stack at the given depth.
+    /// A depth of 0 refers to the top of the return stack.
+    pub fn handle_get_return_element(&self, depth: usize) -> Result<&Cell, Error> {
+        self.return_stack.get(depth)
+    }
+
+    /// Returns the size of the return stack.
+    pub fn handle_return_stack_size(&self) -> usize {
+        self.return_stack.size()
+    }
+
+    /// Removes elements from the return stack until it holds at most `size` elements.
+    pub fn handle_truncate_return_stack(&mut self, size: usize) {
+        self.return_stack.truncate(size);
+    }
+
+    /// Reserves `amount` cells in the data space.
+    /// Returns the address of the first reserved cell.
+    pub fn handle_allot(&mut self, amount: Cell) -> Result<Cell, Error> {
+        let address = self.data_space.allot(amount)?;
+        Ok(Cell::try_from(address).map_err(|_| ForthError::InvalidAddress)?)
+    }
+
+    /// Stores a string in the next free cells of the data space, one byte per cell,
+    /// preceded by its length if it is a counted string, and reserves them,
+    /// as `S"` and `C"` do when they are compiled.
+    /// Returns the values that refer to the string: its address and length,
+    /// or only its address if it is a counted string.
+    pub fn handle_store_string(
+        &mut self,
+        kind: StringKind,
+        text: &[u8],
+    ) -> Result<Vec<Cell>, Error> {
+        let (values, length) = string_cells(kind, text)?;
+        let size = Cell::try_from(values.len()).map_err(|_| ForthError::DataSpaceOverflow)?;
+        let address = self.handle_allot(size)?;
+        for (offset, value) in (0..).zip(values) {
+            self.data_space.store(address + offset, value)?;
+        }
+        Ok(string_reference(kind, address, length))
+    }
+
+    /// Stores a string in the transient region of the data space without reserving it,
+    /// as `S"` and `C"` do when they are interpreted, so that the data being laid out
+    /// with `,` or `ALLOT` is not interrupted.
+    /// Returns the values that refer to the string, as `handle_store_string`.
+    pub fn handle_transient_string(
+        &mut self,
+        kind: StringKind,
+        text: &[u8],
+    ) -> Result<Vec<Cell>, Error> {
+        let (values, length) = string_cells(kind, text)?;
+        let address = self.data_space.append_transient(&values)?;
+        let address = Cell::try_from(address).map_err(|_| ForthError::InvalidAddress)?;
+        Ok(string_reference(kind, address, length))
+    }
+
+    /// Returns the address of the next free cell of the data space.
+    pub fn handle_here(&self) -> Result<Cell, Error> {
+        Ok(Cell::try_from(self.data_space.here()).map_err(|_| ForthError::InvalidAddress)?)
+    }
+
+    /// Releases the cells of the data space allotted from `address` on,
+    /// so that it becomes the next free cell. Nothing is released if it already is free.
+    pub fn handle_restore_here(&mut self, address: Cell) -> Result<(), Error> {
+        let here = self.handle_here()?;
+        if address < here {
+            self.data_space.allot(address - here)?;
+        }
+        Ok(())
+    }
+
+    /// Stores a value at an address of the data space.
+    pub fn handle_store(&mut self, address: Cell, value: Cell) -> Result<(), Error> {
+        self.data_space.store(address, value)
+    }
+
     /// Handles the calculation operations.
+    /// The operands are taken from the stack and the results are pushed in their place.
     fn handle_calculate(&mut self, operation: &str) -> Result<(), Error> {
-        let operand2 = self.stack.drop()?;
-        let operand1 = self.stack.drop()?;
-        let result = self.calculator.calculate(operand1, operand2, operation)?;
-        self.stack.push(result)?;
+        let arity = self
+            .calculator
+            .arity(operation)
+            .ok_or(CalculatorError::UndifiedOperation)?;
+        let mut operands = Vec::with_capacity(arity);
+        for _ in 0..arity {
+            operands.push(self.stack.drop()?);
+        }
+        operands.reverse();
+
+        for result in self.calculator.execute(&operands, operation)? {
+            self.stack.push(result)?;
+        }
         Ok(())
     }
 
     /// Handles the boolean operations.
     fn handle_boolean_operation(&mut self, operation: &BooleanOperation) -> Result<(), Error> {
-        let operand1 = self.stack.drop()?;
-        let operand2 = if self.boolean_manager.is_not(operation) {
-            None
+        let (operand1, operand2) = if self.boolean_manager.is_unary(operation) {
+            (self.stack.drop()?, None)
         } else {
-            Some(self.stack.drop()?)
+            let operand2 = self.stack.drop()?;
+            (self.stack.drop()?, Some(operand2))
         };
         let result = self
             .boolean_manager
@@ -172,37 +374,197 @@ impl<W: Write> ExecutionHandler<W> {
     }
 
     /// Handles the logical operations.
+    /// The operands are taken from the stack and the resulting flag is pushed in their place.
     fn handle_logical_operation(&mut self, operation: &LogicalOperation) -> Result<(), Error> {
-        let operand2 = self.stack.drop()?;
-        let operand1 = self.stack.drop()?;
+        let arity = self.boolean_manager.logical_arity(operation);
+        let mut operands = Vec::with_capacity(arity);
+        for _ in 0..arity {
+            operands.push(self.stack.drop()?);
+        }
+        operands.reverse();
+
         let result = self
             .boolean_manager
-            .execute_logical_operations(operation, operand1, operand2);
+            .execute_logical_operations(operation, &operands);
         self.stack.push(result)?;
         Ok(())
     }
 
     /// Handles the generation output instructions.
     fn handle_generation_output(&mut self, instruction: &Instruction) -> Result<(), Error> {
-        match instruction {
-            Instruction::Output(DOT) => self.handle_output_dot()?,
-            Instruction::Output(CR) => self.handle_output_cr()?,
-            Instruction::Output(EMIT) => self.handle_output_emit()?,
-            Instruction::Output(OutputInstruction::DotQuote(str)) => {
-                self.handle_output_dot_quote(str)?
+        if let Instruction::Output(output) = instruction {
+            self.handle_output(output)?;
+        }
+        Ok(())
+    }
+
+    /// Handles an output instruction, both when interpreted and inside a word.
+    fn handle_output(&mut self, output: &OutputInstruction) -> Result<(), Error> {
+        match output {
+            OutputInstruction::Dot => self.handle_output_dot()?,
+            OutputInstruction::CR => self.handle_output_cr()?,
+            OutputInstruction::Emit => self.handle_output_emit()?,
+            OutputInstruction::DotS => self.handle_output_dot_s()?,
+            OutputInstruction::DotQuote(str) => self.handle_output_dot_quote(str)?,
+            OutputInstruction::LessNumberSign => self.pictured.begin(),
+            OutputInstruction::NumberSign => {
+                let number = self.drop_double()?;
+                let rest = self.pictured.digit(number, self.handle_base());
+                self.push_double(rest)?;
+            }
+            OutputInstruction::NumberSignS => {
+                let number = self.drop_double()?;
+                let rest = self.pictured.digits(number, self.handle_base());
+                self.push_double(rest)?;
+            }
+            OutputInstruction::Hold => {
+                let character = self.stack.drop()?;
+                self.pictured.hold(to_char(character));
+            }
+            OutputInstruction::Sign => {
+                let number = self.stack.drop()?;
+                self.pictured.sign(number);
+            }
+            OutputInstruction::NumberSignGreater => self.handle_number_sign_greater()?,
+            OutputInstruction::Type => self.handle_output_type()?,
+            OutputInstruction::UDot => {
+                let number = self.stack.drop()?;
+                let unsigned = self.handle_cell_size().unsigned(number) as i128;
+                let text = format_in_base(unsigned, self.handle_base());
+                self.write_output(&format!("{} ", text));
+            }
+            OutputInstruction::DotR => {
+                let width = self.stack.drop()?;
+                let number = self.stack.drop()?;
+                self.write_right_aligned(number as i128, width);
+            }
+            OutputInstruction::UDotR => {
+                let width = self.stack.drop()?;
+                let number = self.stack.drop()?;
+                let unsigned = self.handle_cell_size().unsigned(number) as i128;
+                self.write_right_aligned(unsigned, width);
+            }
+            OutputInstruction::Question => {
+                let address = self.stack.drop()?;
+                let value = self.data_space.fetch(address)?;
+                let text = format_in_base(value as i128, self.handle_base());
+                self.write_output(&format!("{} ", text));
+            }
+            OutputInstruction::Dump => self.handle_output_dump()?,
+            OutputInstruction::DotParen(text) => self.write_output(text),
+            OutputInstruction::Space => self.write_output(&BLANK.to_string()),
+            OutputInstruction::Spaces => {
+                let count = self.stack.drop()?;
+                let count = usize::try_from(count).unwrap_or(0);
+                self.write_output(&BLANK.to_string().repeat(count));
             }
-            _ => {}
         }
         Ok(())
     }
 
+    /// Handles the output dump instruction.
+    /// Prints the given number of cells of the data space from the given address,
+    /// a line for every 8 cells starting with the address of the first one.
+    /// Addresses and values are printed in the current base.
+    /// If any of the cells is not allotted, nothing is printed and an error is returned.
+    fn handle_output_dump(&mut self) -> Result<(), Error> {
+        let length = self.stack.drop()?;
+        let address = self.stack.drop()?;
+        let base = self.handle_base();
+        let values = (0..length.max(0))
+            .map(|offset| self.data_space.fetch(address.wrapping_add(offset)))
+            .collect::<Result<Vec<Cell>, Error>>()?;
+
+        let mut text = String::new();
+        for (line, chunk) in values.chunks(DUMP_LINE_CELLS).enumerate() {
+            let line_address = address.wrapping_add((line * DUMP_LINE_CELLS) as Cell);
+            text.push_str(&format_in_base(line_address as i128, base));
+            text.push(':');
+            for value in chunk {
+                text.push(' ');
+                text.push_str(&format_in_base(*value as i128, base));
+            }
+            text.push('\n');
+        }
+        self.write_output(&text);
+        Ok(())
+    }
+
+    /// Takes an unsigned double-cell number from the stack, with its high cell on top.
+    fn drop_double(&mut self) -> Result<u128, Error> {
+        let high = self.stack.drop()?;
+        let low = self.stack.drop()?;
+        Ok(self.handle_cell_size().join_double(low, high))
+    }
+
+    /// Pushes an unsigned double-cell number onto the stack, with its high cell on top.
+    fn push_double(&mut self, number: u128) -> Result<(), Error> {
+        let (low, high) = self.handle_cell_size().split_double(number);
+        self.stack.push(low)?;
+        self.stack.push(high)
+    }
+
+    /// Handles the end of the pictured numeric output (`#>`).
+    /// Drops the double-cell number and pushes the address and the length of the string,
+    /// which is left in the transient region of the data space encoded in UTF-8, like `S"`.
+    fn handle_number_sign_greater(&mut self) -> Result<(), Error> {
+        self.drop_double()?;
+        let characters: Vec<Cell> = self.pictured.finish().bytes().map(Cell::from).collect();
+        let address = self.data_space.transient(&characters)?;
+        self.stack
+            .push(Cell::try_from(address).map_err(|_| ForthError::InvalidAddress)?)?;
+        self.stack.push(characters.len() as Cell)
+    }
+
+    /// Handles the output type instruction.
+    /// Prints the string of the given length stored at the given address of the data space.
+    /// Each cell holds a byte of the UTF-8 encoding of the string, which is written as it is,
+    /// so that characters taking several bytes are printed whole.
+    fn handle_output_type(&mut self) -> Result<(), Error> {
+        let length = self.stack.drop()?;
+        let address = self.stack.drop()?;
+        let mut bytes = Vec::new();
+        for offset in 0..length.max(0) {
+            let byte = self.data_space.fetch(address.wrapping_add(offset))? & CHAR_MASK;
+            bytes.push(byte as u8);
+        }
+        if let Some(writer) = &mut self.writer {
+            let _ = writer.write_all(&bytes);
+            let _ = writer.flush();
+        }
+        Ok(())
+    }
+
+    /// Writes a number in the current base, right-aligned in a field of the given width.
+    /// Longer numbers are written in full.
+    fn write_right_aligned(&mut self, number: i128, width: Cell) {
+        let text = format_in_base(number, self.handle_base());
+        let width = usize::try_from(width).unwrap_or(0);
+        self.write_output(&format!("{:>width$}", text));
+    }
+
+    /// Writes text to the writer, if it exists, exactly as it is.
+    pub fn handle_write(&mut self, text: &str) {
+        self.write_output(text);
+    }
+
+    /// Writes text to the writer, if it exists.
+    fn write_output(&mut self, text: &str) {
+        if let Some(writer) = &mut self.writer {
+            let _ = write!(writer, "{}", text);
+            let _ = writer.flush();
+        }
+    }
+
     /// Handles the output dot instruction.
+    /// The number is printed in the current base.
     fn handle_output_dot(&mut self) -> Result<(), Error> {
-        if let Ok(top) = self.stack.drop() {
-            if let Some(writer) = &mut self.writer {
-                let _ = write!(writer, "{} ", top);
-                let _ = writer.flush();
-            }
+        let base = self.handle_base();
+        if let Ok(top) = self.stack.drop()
+            && let Some(writer) = &mut self.writer
+        {
+            let _ = write!(writer, "{} ", format_in_base(top as i128, base));
+            let _ = writer.flush();
         }
         Ok(())
     }
@@ -217,32 +579,93 @@ impl<W: Write> ExecutionHandler<W> {
     }
 
     /// Handles the output emit instruction.
+    /// The code is written as the UTF-8 encoding of its character, the one that the
+    /// character literals and `HOLD` use, so `233 EMIT` and `'é' EMIT` both print `é`.
+    /// In the legacy output mode, the character is followed by a space.
     fn handle_output_emit(&mut self) -> Result<(), Error> {
-        if let Ok(top) = self.stack.drop() {
-            if let Ok(ascii_char) = u8::try_from(top) {
-                if let Some(writer) = &mut self.writer {
-                    let _ = write!(writer, "{} ", ascii_char as char);
-                    let _ = writer.flush();
-                }
+        let separator = self.output_separator();
+        if let Ok(top) = self.stack.drop()
+            && let Some(writer) = &mut self.writer
+        {
+            let _ = write!(writer, "{}{}", to_char(top), separator);
+            let _ = writer.flush();
+        }
+        Ok(())
+    }
+
+    /// Handles the output dot-s instruction.
+    /// Prints the depth of the stack followed by its elements, from bottom to top,
+    /// in the current base.
+    fn handle_output_dot_s(&mut self) -> Result<(), Error> {
+        let base = self.handle_base();
+        if let Some(writer) = &mut self.writer {
+            let content = self.stack.get_stack_content();
+            let _ = write!(writer, "<{}> ", content.len());
+            for element in content {
+                let _ = write!(writer, "{} ", format_in_base(*element as i128, base));
             }
+            let _ = writer.flush();
         }
         Ok(())
     }
 
     /// Handles the output dot quote instruction.
+    /// In the legacy output mode, the string is followed by a space.
     fn handle_output_dot_quote(&mut self, string: &str) -> Result<(), Error> {
+        let separator = self.output_separator();
         if let Some(writer) = &mut self.writer {
-            let _ = write!(writer, "{} ", string);
+            let _ = write!(writer, "{}{}", string, separator);
             let _ = writer.flush();
         }
         Ok(())
     }
+
+    /// Returns what follows the output of `EMIT` and `."` in the current output mode.
+    fn output_separator(&self) -> &'static str {
+        match self.output_mode {
+            OutputMode::Legacy => " ",
+            OutputMode::Standard => "",
+        }
+    }
+}
+
+/// Converts a cell into the character whose code it holds, as the character literals
+/// (e.g. `'€'`) leave it. Codes that are not characters are replaced by `U+FFFD`.
+fn to_char(value: Cell) -> char {
+    u32::try_from(value)
+        .ok()
+        .and_then(char::from_u32)
+        .unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// Converts the bytes of a string, usually its UTF-8 encoding, into the cells that hold it,
+/// one byte per cell, preceded by its length in bytes if it is a counted string.
+/// Returns the cells and the length of the string.
+fn string_cells(kind: StringKind, text: &[u8]) -> Result<(Vec<Cell>, Cell), Error> {
+    let mut values: Vec<Cell> = text.iter().copied().map(Cell::from).collect();
+    let length = Cell::try_from(values.len()).map_err(|_| ForthError::DataSpaceOverflow)?;
+    if kind == StringKind::Counted {
+        values.insert(0, length);
+    }
+    Ok((values, length))
+}
+
+/// Returns the values that refer to a string stored at `address`:
+/// its address and length, or only its address if it is a counted string.
+fn string_reference(kind: StringKind, address: Cell, length: Cell) -> Vec<Cell> {
+    match kind {
+        StringKind::Plain => vec![address, length],
+        StringKind::Counted => vec![address],
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::stack::stack_operations::{DROP, DUP, OVER, ROT, SWAP};
+    use crate::stack::stack_errors::StackError;
+    use crate::stack::stack_operations::{
+        DROP, DUP, OVER, R_FETCH, R_FROM, ROT, SWAP, TO_R, TWO_R_FROM, TWO_TO_R,
+    };
     use std::io::Sink;
 
     #[test]
@@ -289,4 +712,126 @@ mod tests {
 
         assert_eq!(handler.stack.get_stack_content(), &expected_result);
     }
+
+    #[test]
+    fn test_handle_return_stack_transfers() {
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let instructions: Vec<Instruction> = vec![
+            Instruction::number(1),
+            Instruction::number(2),
+            Instruction::number(3),
+            Instruction::stack_word(TWO_TO_R),
+            Instruction::stack_word(TO_R),
+            Instruction::stack_word(R_FETCH),
+            Instruction::stack_word(R_FROM),
+            Instruction::stack_word(TWO_R_FROM),
+        ];
+        let expected_result = vec![1, 1, 2, 3];
+
+        for instruction in instructions {
+            handler.handle_instruction(&instruction).unwrap();
+        }
+
+        assert_eq!(handler.stack.get_stack_content(), &expected_result);
+        assert_eq!(handler.handle_return_stack_size(), 0);
+    }
+
+    #[test]
+    fn test_handle_return_stack_underflow() {
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+
+        let result = handler.handle_instruction(&Instruction::stack_word(R_FROM));
+
+        assert_eq!(result, Err(StackError::ReturnStackUnderflow.into()));
+    }
+
+    #[test]
+    fn strings_are_stored_in_the_data_space() {
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let here = handler.handle_here().unwrap();
+
+        let plain = handler.handle_store_string(StringKind::Plain, "hi".as_bytes());
+        let counted = handler.handle_store_string(StringKind::Counted, "hi".as_bytes());
+
+        assert_eq!(plain, Ok(vec![here, 2]));
+        assert_eq!(counted, Ok(vec![here + 2]));
+        assert_eq!(handler.handle_here(), Ok(here + 5));
+        assert_eq!(handler.data_space.fetch(here + 2), Ok(2));
+        assert_eq!(handler.data_space.fetch(here + 4), Ok('i' as Cell));
+    }
+
+    #[test]
+    fn interpreted_strings_are_not_allotted() {
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let here = handler.handle_here().unwrap();
+
+        let first = handler.handle_transient_string(StringKind::Plain, "ab".as_bytes());
+        let second = handler.handle_transient_string(StringKind::Counted, "c".as_bytes());
+        handler.data_space.comma(7).unwrap();
+
+        assert_eq!(first, Ok(vec![here, 2]));
+        assert_eq!(second, Ok(vec![here + 2]));
+        assert_eq!(handler.handle_here(), Ok(here + 1));
+        assert_eq!(handler.data_space.fetch(here), Ok(7));
+    }
+
+    #[test]
+    fn strings_are_stored_and_typed_as_utf8() {
+        let mut handler = ExecutionHandler::new(None, Some(Vec::new()));
+
+        let values = handler
+            .handle_transient_string(StringKind::Plain, "€uro ñ".as_bytes())
+            .unwrap();
+        for value in values.iter() {
+            handler.handle_push_element(*value).unwrap();
+        }
+        handler
+            .handle_instruction(&Instruction::output(OutputInstruction::Type))
+            .unwrap();
+
+        assert_eq!(values[1], 9);
+        let output = String::from_utf8(handler.handle_get_writer().unwrap().to_vec()).unwrap();
+        assert_eq!(output, "€uro ñ");
+    }
+
+    #[test]
+    fn emit_writes_the_utf8_encoding_of_the_character() {
+        let mut handler =
+            ExecutionHandler::new(None, Some(Vec::new())).with_output_mode(OutputMode::Standard);
+
+        for code in [233, '€' as Cell, 65] {
+            handler.handle_push_element(code).unwrap();
+            handler
+                .handle_instruction(&Instruction::output(OutputInstruction::Emit))
+                .unwrap();
+        }
+
+        let output = String::from_utf8(handler.handle_get_writer().unwrap().to_vec()).unwrap();
+        assert_eq!(output, "é€A");
+    }
+
+    #[test]
+    fn strings_that_do_not_fit_are_not_stored() {
+        let mut handler: ExecutionHandler<Sink> =
+            ExecutionHandler::new(None, None).with_data_space_size(Some(8));
+        let here = handler.handle_here().unwrap();
+
+        let result = handler.handle_store_string(StringKind::Plain, "too long".as_bytes());
+
+        assert_eq!(result, Err(ForthError::DataSpaceOverflow.into()));
+        assert_eq!(handler.handle_here(), Ok(here));
+    }
+
+    #[test]
+    fn restoring_here_releases_the_cells_allotted_after_it() {
+        let mut handler: ExecutionHandler<Sink> = ExecutionHandler::new(None, None);
+        let here = handler.handle_allot(2).unwrap();
+        let _ = handler.handle_allot(3);
+
+        handler.handle_restore_here(here + 1).unwrap();
+        handler.handle_restore_here(here + 4).unwrap();
+
+        assert_eq!(handler.handle_here(), Ok(here + 1));
+        assert!(handler.data_space.fetch(here + 1).is_err());
+    }
 }
@@ -1,64 +1,250 @@
 pub mod calculator;
+pub mod cell;
 pub mod errors;
 pub mod forth;
 pub mod handler;
+pub mod memory;
 pub mod stack;
 
 pub use forth::boolean_operations::{BooleanOperation, LogicalOperation};
 pub use forth::interpreter::Forth;
 pub use forth::intruction::Instruction;
-use forth::parser::Parser;
+use forth::parser::{Parser, strip_comments};
 pub use stack::core::Stack;
 
-use crate::errors::Error;
+use crate::calculator::arithmetic_policy::ArithmeticPolicy;
+use crate::calculator::division_mode::DivisionMode;
+use crate::cell::CellSize;
+use crate::errors::{Error, ErrorFormat};
+use crate::forth::boolean_operations::BooleanMode;
+use crate::forth::output_instructions::OutputMode;
+use crate::forth::word::CaseSensitivity;
 use std::fs::File;
 use std::io::{self, BufRead, BufWriter, Write};
 
 /// Configuration struct for the Forth interpreter
-/// Contains the file path and optional stack size
-/// The file path is required, while the stack size is optional
-/// The stack size is specified in bytes
+/// Contains the file path, optional stack size, optional data space size
+/// and optional maximum call depth, whether the strict compile mode is enabled,
+/// the format used to report errors, the width of the cells, the arithmetic policy,
+/// the division mode, the boolean mode, the output mode and the case sensitivity of the words
+/// The file path is required unless the interpreter runs in REPL mode,
+/// while the other options are optional
+/// The stack size and the data space size are specified in bytes
 #[derive(Debug, PartialEq)]
 pub struct Config {
     pub file_path: String,
+    pub repl: bool,
     pub stack_size: Option<usize>,
+    pub data_space_size: Option<usize>,
+    pub max_call_depth: Option<usize>,
+    pub strict: bool,
+    pub error_format: ErrorFormat,
+    pub cell_size: CellSize,
+    pub arithmetic_policy: ArithmeticPolicy,
+    pub division_mode: DivisionMode,
+    pub boolean_mode: BooleanMode,
+    pub output_mode: OutputMode,
+    pub case_sensitivity: CaseSensitivity,
 }
 
 impl Config {
     /// Constructs a new `Config` instance
     /// Takes a slice of strings as arguments and a parser
     /// Returns a `Result` containing the `Config` instance or an error
-    /// If the file path is empty or not provided, it returns a `MissingPathError`
-    /// If the stack size is provided, it attempts to parse it
-    /// If parsing fails, it prints an error message and uses the default stack size
+    /// The file path is the argument that is neither a `--flag` nor a `key=value` setting,
+    /// and it is looked for once the options have been parsed
+    /// If no file path is provided or the `--repl` argument is received, the REPL mode is enabled
+    /// If the file path is empty, it returns a `MissingPathError`
+    /// Unknown options, and arguments after the file path that are not options,
+    /// return an `UnknownOption` error
+    /// If the stack size, the data space size or the maximum call depth are provided,
+    /// it attempts to parse them
+    /// If parsing fails, it prints an error message and uses the default value
+    /// The `--strict` argument enables the strict compile mode
+    /// The `error-format=rich` argument reports errors with their location
+    /// The `cell-size=32` argument selects the width of the cells (16, 32 or 64 bits)
+    /// The `arithmetic=checked` argument selects what happens when a result does not fit
+    /// in a cell (`wrapping`, `checked` or `saturating`)
+    /// The `division=floored` argument rounds quotients towards negative infinity
+    /// The `boolean=logical` argument keeps the logical `AND`, `OR` and `XOR` of the
+    /// original interpreter instead of the bitwise ones
+    /// The `output=standard` argument prints `EMIT` and `."` without the trailing space
+    /// of the original interpreter
+    /// The `case=sensitive` argument makes the names of user-defined words case sensitive
     pub fn build(args: &[String], parser: &Parser) -> Result<Config, Error> {
-        if args.len() < 2 || args[1].is_empty() {
-            return Err(Error::MissingPathError);
+        let (paths, options): (Vec<&String>, Vec<&String>) =
+            args.iter().skip(1).partition(|arg| !is_option(arg));
+
+        let mut config = Config {
+            file_path: String::new(),
+            repl: false,
+            stack_size: None,
+            data_space_size: None,
+            max_call_depth: None,
+            strict: false,
+            error_format: ErrorFormat::default(),
+            cell_size: CellSize::default(),
+            arithmetic_policy: ArithmeticPolicy::default(),
+            division_mode: DivisionMode::default(),
+            boolean_mode: BooleanMode::default(),
+            output_mode: OutputMode::default(),
+            case_sensitivity: CaseSensitivity::default(),
+        };
+        config.parse_options(options.into_iter(), parser)?;
+
+        let mut paths = paths.into_iter();
+        match paths.next() {
+            Some(path) if path.is_empty() => return Err(Error::MissingPathError),
+            Some(path) => config.file_path = path.to_string(),
+            None => config.repl = true,
         }
+        if let Some(argument) = paths.next() {
+            return Err(Error::UnknownOption(argument.to_string()));
+        }
+        Ok(config)
+    }
 
-        let mut stack_size = None;
-        if args.len() == 3 && !args[2].is_empty() {
-            if let Ok(size) = parser.parse_stack_size(&args[2]) {
-                stack_size = Some(size);
+    /// Parses the optional settings received as `key=value` arguments and the `--flag` ones
+    /// Returns an `UnknownOption` error for the options that are not recognized
+    fn parse_options<'a>(
+        &mut self,
+        args: impl Iterator<Item = &'a String>,
+        parser: &Parser,
+    ) -> Result<(), Error> {
+        for arg in args {
+            if arg == REPL_FLAG {
+                self.repl = true;
+            } else if arg == STRICT_FLAG {
+                self.strict = true;
+            } else if arg.starts_with("data-space-size=") {
+                if let Ok(size) = parser.parse_data_space_size(arg) {
+                    self.data_space_size = Some(size);
+                } else {
+                    println!("invalid data space size");
+                    println!("using default data space size");
+                }
+            } else if arg.starts_with("error-format=") {
+                if let Ok(error_format) = parser.parse_error_format(arg) {
+                    self.error_format = error_format;
+                } else {
+                    println!("invalid error format");
+                    println!("using terse error format");
+                }
+            } else if arg.starts_with("arithmetic=") {
+                if let Ok(policy) = parser.parse_arithmetic_policy(arg) {
+                    self.arithmetic_policy = policy;
+                } else {
+                    println!("invalid arithmetic policy");
+                    println!("using wrapping arithmetic");
+                }
+            } else if arg.starts_with("boolean=") {
+                if let Ok(boolean_mode) = parser.parse_boolean_mode(arg) {
+                    self.boolean_mode = boolean_mode;
+                } else {
+                    println!("invalid boolean mode");
+                    println!("using bitwise boolean operations");
+                }
+            } else if arg.starts_with("output=") {
+                if let Ok(output_mode) = parser.parse_output_mode(arg) {
+                    self.output_mode = output_mode;
+                } else {
+                    println!("invalid output mode");
+                    println!("using legacy output");
+                }
+            } else if arg.starts_with("case=") {
+                if let Ok(case_sensitivity) = parser.parse_case_sensitivity(arg) {
+                    self.case_sensitivity = case_sensitivity;
+                } else {
+                    println!("invalid case sensitivity");
+                    println!("using case insensitive words");
+                }
+            } else if arg.starts_with("division=") {
+                if let Ok(division_mode) = parser.parse_division_mode(arg) {
+                    self.division_mode = division_mode;
+                } else {
+                    println!("invalid division mode");
+                    println!("using symmetric division");
+                }
+            } else if arg.starts_with("cell-size=") {
+                if let Ok(cell_size) = parser.parse_cell_size(arg) {
+                    self.cell_size = cell_size;
+                } else {
+                    println!("invalid cell size");
+                    println!("using default cell size");
+                }
+            } else if arg.starts_with("max-call-depth=") {
+                if let Ok(depth) = parser.parse_max_call_depth(arg) {
+                    self.max_call_depth = Some(depth);
+                } else {
+                    println!("invalid max call depth");
+                    println!("using default max call depth");
+                }
+            } else if arg.starts_with("stack-size=") {
+                if let Ok(size) = parser.parse_stack_size(arg) {
+                    self.stack_size = Some(size);
+                } else {
+                    println!("invalid stack size");
+                    println!("using default stack size");
+                }
             } else {
-                println!("invalid stack size");
-                println!("using default stack size");
+                return Err(Error::UnknownOption(arg.to_string()));
             }
         }
+        Ok(())
+    }
 
-        let file_path = args[1].to_string();
-
-        Ok(Config {
-            file_path,
-            stack_size,
-        })
+    /// Creates a Forth interpreter with the settings of the configuration
+    fn build_forth<W: Write>(&self, writer: W) -> Forth<W> {
+        Forth::new(self.stack_size, Some(writer))
+            .with_cell_size(self.cell_size)
+            .with_arithmetic_policy(self.arithmetic_policy)
+            .with_division_mode(self.division_mode)
+            .with_boolean_mode(self.boolean_mode)
+            .with_output_mode(self.output_mode)
+            .with_case_sensitivity(self.case_sensitivity)
+            .with_data_space_size(self.data_space_size)
+            .with_max_call_depth(self.max_call_depth)
+            .with_strict_mode(self.strict)
     }
 }
 
+/// Argument that starts the interpreter in REPL mode
+const REPL_FLAG: &str = "--repl";
+
+/// Argument that enables the strict compile mode
+const STRICT_FLAG: &str = "--strict";
+
+/// Keys of the settings received as `key=value` arguments
+const OPTION_KEYS: [&str; 10] = [
+    "data-space-size",
+    "error-format",
+    "arithmetic",
+    "boolean",
+    "output",
+    "case",
+    "division",
+    "cell-size",
+    "max-call-depth",
+    "stack-size",
+];
+
+/// Checks if an argument is an option, either a `--flag` or a `key=value` setting
+/// with a known key, rather than the file path, which may contain `=` too
+fn is_option(arg: &str) -> bool {
+    arg.starts_with("--")
+        || arg
+            .split_once('=')
+            .is_some_and(|(key, _)| OPTION_KEYS.contains(&key))
+}
+
+/// Word that ends a REPL session
+const REPL_EXIT_WORD: &str = "bye";
+
 /// Runs the Forth interpreter with the given configuration
 ///
 /// Takes a `Config` instance as an argument
-/// Reads the input file line by line
+/// If the REPL mode is enabled, reads the instructions from the standard input
+/// Otherwise, reads the input file line by line
 ///
 /// Parses the instructions and processes them
 ///
@@ -66,31 +252,96 @@ impl Config {
 ///
 /// Returns a `Result` indicating success or failure
 pub fn run(config: Config) -> Result<(), Box<dyn std::error::Error>> {
+    if config.repl {
+        let mut forth = config.build_forth(io::BufWriter::new(io::stdout()));
+        repl(&mut forth, io::stdin().lock(), config.error_format)?;
+        return Ok(());
+    }
+
     let file = File::open(&config.file_path)?;
     let reader = io::BufReader::new(file);
-    let writer = io::BufWriter::new(io::stdout());
-    let mut forth = Forth::new(config.stack_size, Some(writer));
+    let mut forth = config
+        .build_forth(io::BufWriter::new(io::stdout()))
+        .with_source_name(config.file_path.to_string());
     let stack_output = File::create("stack.fth")?;
     let mut stack_writer = io::BufWriter::new(stack_output);
 
     let input = reader
         .lines()
         .map_while(|line| line.ok())
-        .map(|line| line.trim().to_string())
-        .filter(|line| !line.is_empty())
         .collect::<Vec<_>>()
         .join("\n");
 
-    let unified_input = unify_multiline_definitions(input);
-
-    for line in unified_input.lines() {
-        let tokens = forth.parse_instructions(line.to_lowercase());
-        forth.process_instructions(tokens)?;
+    for (line_number, line) in unify_multiline_definitions(input) {
+        forth
+            .parse_with_spans(line, line_number)
+            .and_then(|(instructions, spans)| {
+                forth.process_instructions_with_spans(instructions, spans)
+            })
+            .map_err(|error| config.error_format.apply(forth.attach_traceback(error)))?;
         write_stack_output(&forth, &mut stack_writer)?;
     }
     Ok(())
 }
 
+/// Runs an interactive session of the Forth interpreter
+///
+/// Reads the input line by line, keeping the words defined in previous lines
+/// Definitions that span several lines are accumulated until their `;` is found,
+/// printing ` compiled` meanwhile
+/// After every line processed successfully it prints ` ok`, like gforth
+/// If a line fails, the error is printed using `error_format` and the session continues
+/// The session ends when the input is exhausted or the word `bye` is received
+///
+/// # Examples
+/// ```
+///# use rust_forth::{Forth, repl, errors::ErrorFormat};
+/// let mut forth = Forth::new(None, Some(Vec::new()));
+/// let input = "2 3 + .\n".as_bytes();
+///
+/// repl(&mut forth, input, ErrorFormat::Terse).unwrap();
+///
+/// let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+/// assert_eq!(output, "5  ok\n");
+/// ```
+pub fn repl<R: BufRead, W: Write>(
+    forth: &mut Forth<W>,
+    input: R,
+    error_format: ErrorFormat,
+) -> Result<(), io::Error> {
+    let mut definition = MultilineDefinition::default();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().eq_ignore_ascii_case(REPL_EXIT_WORD) {
+            break;
+        }
+
+        let message = match definition.push_line(index + 1, &line) {
+            Some((line_number, complete_line)) => {
+                let result = forth.parse_with_spans(complete_line, line_number).and_then(
+                    |(instructions, spans)| {
+                        forth.process_instructions_with_spans(instructions, spans)
+                    },
+                );
+                match result {
+                    Ok(()) => " ok".to_string(),
+                    Err(error) => error_format
+                        .apply(forth.attach_traceback(error))
+                        .to_string(),
+                }
+            }
+            None => " compiled".to_string(),
+        };
+
+        if let Some(writer) = forth.get_writer() {
+            writeln!(writer, "{}", message)?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
 /// Writes the current stack output to a file
 fn write_stack_output<W: Write>(
     forth: &Forth<W>,
@@ -108,43 +359,75 @@ fn write_stack_output<W: Write>(
 
 /// Unifies multiline definitions in the input string
 /// This function takes a string input and processes it line by line.
-/// It looks for lines that start with a colon (:) and end with a semicolon (;)
-/// and combines them into a single line.
-/// It returns a new string with the unified definitions.
-fn unify_multiline_definitions(input: String) -> String {
+/// It looks for lines that start with a colon (:) and end with a semicolon (;),
+/// as well as parenthesised comments that span several lines,
+/// and combines them into a single entry, keeping their line breaks.
+/// Empty lines are skipped.
+/// It returns the unified entries, each one with the number of the line where it starts.
+fn unify_multiline_definitions(input: String) -> Vec<(usize, String)> {
     let mut unified_lines = Vec::new();
-    let mut current_definition = String::new();
-    let mut in_definition = false;
-
-    for line in input.lines() {
-        let trimmed_line = line.trim();
-
-        if trimmed_line.starts_with(":") && trimmed_line.ends_with(";") {
-            unified_lines.push(trimmed_line.to_string());
-            current_definition.clear();
-            in_definition = false;
-        } else if trimmed_line.starts_with(":") {
-            in_definition = true;
-            current_definition.push_str(trimmed_line);
-            current_definition.push(' ');
-        } else if in_definition {
-            current_definition.push_str(trimmed_line);
-            current_definition.push(' ');
-
-            if trimmed_line.ends_with(";") {
-                unified_lines.push(current_definition.trim().to_string());
-                current_definition.clear();
-                in_definition = false;
-            }
-        } else {
-            unified_lines.push(trimmed_line.to_string());
+    let mut definition = MultilineDefinition::default();
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(complete_line) = definition.push_line(index + 1, line) {
+            unified_lines.push(complete_line);
         }
     }
 
-    if !current_definition.is_empty() {
-        unified_lines.push(current_definition.trim().to_string());
+    if let Some(pending_definition) = definition.pending() {
+        unified_lines.push(pending_definition);
+    }
+    unified_lines
+}
+
+/// Accumulates the lines of a definition, a parenthesised comment or a string that spans several lines
+/// A line that starts with a colon (:) opens a definition,
+/// which is completed by the first line that ends with a semicolon (;)
+/// A comment opened with `(` is completed by the first line with a `)`,
+/// and the text of a string word by the first line with its closing delimiter
+/// Comments are ignored when looking for the colon and the semicolon
+#[derive(Default)]
+struct MultilineDefinition {
+    current_definition: String,
+    first_line: usize,
+}
+
+impl MultilineDefinition {
+    /// Adds a line to the accumulator, with its number
+    /// Returns the complete entry to be processed with the number of the line where it starts,
+    /// or `None` if a definition, a comment or a string is still open
+    /// The lines of a definition are kept with their indentation, so that the columns
+    /// of their tokens are preserved
+    fn push_line(&mut self, line_number: usize, line: &str) -> Option<(usize, String)> {
+        if self.current_definition.is_empty() {
+            self.first_line = line_number;
+        }
+        self.current_definition.push_str(line);
+        self.current_definition.push('\n');
+
+        let (code, open_comment) = strip_comments(&self.current_definition);
+        let code = code.trim();
+        if open_comment || (code.starts_with(":") && !code.ends_with(";")) {
+            return None;
+        }
+        let complete_definition = self.current_definition.trim_end().to_string();
+        self.current_definition.clear();
+        Some((self.first_line, complete_definition))
+    }
+
+    /// Returns the definition that was left open, if any, with the number of its first line
+    fn pending(self) -> Option<(usize, String)> {
+        if self.current_definition.is_empty() {
+            return None;
+        }
+        Some((
+            self.first_line,
+            self.current_definition.trim_end().to_string(),
+        ))
     }
-    unified_lines.join("\n")
 }
 
 #[cfg(test)]
@@ -169,13 +452,289 @@ mod tests {
     }
 
     #[test]
-    fn try_build_config_without_path_throw_error() {
+    fn can_parse_data_space_size_recibed_correctly() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "stack-size=10".to_string(),
+            "data-space-size=512".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.stack_size, Some(10));
+        assert_eq!(config.data_space_size, Some(512));
+    }
+
+    #[test]
+    fn can_parse_max_call_depth_recibed_correctly() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "max-call-depth=64".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.max_call_depth, Some(64));
+        assert_eq!(config.stack_size, None);
+    }
+
+    #[test]
+    fn can_parse_cell_size_recibed_correctly() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "cell-size=64".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.cell_size, CellSize::I64);
+        assert_eq!(config.stack_size, None);
+    }
+
+    #[test]
+    fn can_parse_arithmetic_policy_recibed_correctly() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "arithmetic=saturating".to_string(),
+            "cell-size=32".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.arithmetic_policy, ArithmeticPolicy::Saturating);
+        assert_eq!(config.cell_size, CellSize::I32);
+    }
+
+    #[test]
+    fn can_parse_division_mode_recibed_correctly() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "division=floored".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.division_mode, DivisionMode::Floored);
+    }
+
+    #[test]
+    fn can_parse_boolean_mode_recibed_correctly() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "boolean=logical".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.boolean_mode, BooleanMode::Logical);
+    }
+
+    #[test]
+    fn can_parse_output_mode_recibed_correctly() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "output=standard".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.output_mode, OutputMode::Standard);
+    }
+
+    #[test]
+    fn can_parse_case_sensitivity_recibed_correctly() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "case=sensitive".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.case_sensitivity, CaseSensitivity::Sensitive);
+    }
+
+    #[test]
+    fn can_enable_strict_mode() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "--strict".to_string(),
+            "stack-size=10".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert!(config.strict);
+        assert_eq!(config.stack_size, Some(10));
+    }
+
+    #[test]
+    fn flags_without_path_enable_repl() {
+        let args = vec!["program_name".to_string(), "--strict".to_string()];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert!(config.repl);
+        assert!(config.strict);
+        assert_eq!(config.file_path, "");
+    }
+
+    #[test]
+    fn unknown_options_are_reported() {
+        let parser = Parser::new();
+
+        for option in ["--verbose", "colour=red", "other_file.fth"] {
+            let args = vec![
+                "program_name".to_string(),
+                "path_to_file.fth".to_string(),
+                option.to_string(),
+            ];
+
+            let config = Config::build(&args, &parser);
+
+            assert_eq!(config, Err(Error::UnknownOption(option.to_string())));
+        }
+    }
+
+    #[test]
+    fn paths_with_an_equals_sign_are_not_options() {
+        let args = vec![
+            "program_name".to_string(),
+            "stack-size=10".to_string(),
+            "out=1.fth".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.file_path, "out=1.fth");
+        assert_eq!(config.stack_size, Some(10));
+        assert!(!config.repl);
+    }
+
+    #[test]
+    fn build_config_without_path_enables_repl() {
         let args = vec!["program_name".to_string()];
 
         let parser = Parser::new();
-        let config = Config::build(&args, &parser);
+        let config = Config::build(&args, &parser).unwrap();
 
-        assert_eq!(config, Err(Error::MissingPathError));
+        assert!(config.repl);
+        assert_eq!(config.stack_size, None);
+    }
+
+    #[test]
+    fn can_build_repl_config_with_stack_size() {
+        let args = vec![
+            "program_name".to_string(),
+            "--repl".to_string(),
+            "stack-size=16".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert!(config.repl);
+        assert_eq!(config.stack_size, Some(16));
+    }
+
+    #[test]
+    fn repl_keeps_definitions_and_recovers_from_errors() {
+        let mut forth = Forth::new(None, Some(Vec::new()));
+        let input = ": square\ndup * ;\ndrop drop\n3 square .s\nbye\n4".as_bytes();
+        let expected_result = " compiled\n ok\nstack-underflow\n<1> 9  ok\n";
+
+        let result = repl(&mut forth, input, ErrorFormat::Terse);
+        let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(output, expected_result);
+        assert_eq!(forth.get_stack_content(), &vec![9]);
+    }
+
+    #[test]
+    fn repl_keeps_the_case_of_strings_that_span_several_lines() {
+        let mut forth = Forth::new(None, Some(Vec::new()));
+        let input = ".\"Hello\" S\" Two\nLines\" TYPE".as_bytes();
+        let expected_result = " compiled\nHello Two\nLines ok\n";
+
+        let result = repl(&mut forth, input, ErrorFormat::Terse);
+        let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(output, expected_result);
+    }
+
+    #[test]
+    fn repl_warns_about_redefined_words_like_gforth() {
+        let mut forth = Forth::new(None, Some(Vec::new()));
+        let input = ": foo 1 ;\n: foo 2 ;\nforget foo foo".as_bytes();
+        let expected_result = " ok\nredefined foo\n ok\n ok\n";
+
+        let result = repl(&mut forth, input, ErrorFormat::Terse);
+        let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(output, expected_result);
+        assert_eq!(forth.get_stack_content(), &vec![1]);
+    }
+
+    #[test]
+    fn can_unify_multiline_definitions() {
+        let input = ": foo\n  1 2\n+ ;\n\nfoo\n: bar".to_string();
+        let expected_result = vec![
+            (1, ": foo\n  1 2\n+ ;".to_string()),
+            (5, "foo".to_string()),
+            (6, ": bar".to_string()),
+        ];
+
+        let result = unify_multiline_definitions(input);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_unify_multiline_strings() {
+        let input = "s\" first\nsecond\" type\n.( ok )".to_string();
+        let expected_result = vec![
+            (1, "s\" first\nsecond\" type".to_string()),
+            (3, ".( ok )".to_string()),
+        ];
+
+        let result = unify_multiline_definitions(input);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn can_unify_multiline_comments() {
+        let input = "( a comment\n  that ends ) 1\n: foo ( n -- n ) \\ ;\n;".to_string();
+        let expected_result = vec![
+            (1, "( a comment\n  that ends ) 1".to_string()),
+            (3, ": foo ( n -- n ) \\ ;\n;".to_string()),
+        ];
+
+        let result = unify_multiline_definitions(input);
+
+        assert_eq!(result, expected_result);
     }
 
     #[test]
@@ -191,4 +750,58 @@ mod tests {
 
         assert_eq!(config, Err(Error::MissingPathError));
     }
+
+    #[test]
+    fn repl_reports_unknown_words_in_strict_mode() {
+        let mut forth = Forth::new(None, Some(Vec::new())).with_strict_mode(true);
+        let input = ": foo 1 bar ;\nfoo".as_bytes();
+        let expected_result = "bar ? at column 9\n?\n";
+
+        let result = repl(&mut forth, input, ErrorFormat::Terse);
+        let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(output, expected_result);
+    }
+
+    #[test]
+    fn repl_locates_unknown_words_in_strict_mode() {
+        let mut forth = Forth::new(None, Some(Vec::new())).with_strict_mode(true);
+        let input = ": foo 1 bar ;\nfoo".as_bytes();
+        let expected_result = "1:9: bar ? in 'bar'\n2:1: ? in 'foo'\n";
+
+        let result = repl(&mut forth, input, ErrorFormat::Rich);
+        let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(output, expected_result);
+    }
+
+    #[test]
+    fn can_parse_error_format() {
+        let args = vec![
+            "program_name".to_string(),
+            "path_to_file.fth".to_string(),
+            "error-format=rich".to_string(),
+        ];
+
+        let parser = Parser::new();
+        let config = Config::build(&args, &parser).unwrap();
+
+        assert_eq!(config.error_format, ErrorFormat::Rich);
+    }
+
+    #[test]
+    fn repl_locates_errors_inside_multiline_definitions() {
+        let mut forth = Forth::new(None, Some(Vec::new()));
+        let input = ": double\n  dup + ;\ndouble".as_bytes();
+        let expected_result =
+            " compiled\n ok\n2:3: stack-underflow in 'double'\n  in double\n  at line 3\n";
+
+        let result = repl(&mut forth, input, ErrorFormat::Rich);
+        let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(output, expected_result);
+    }
 }
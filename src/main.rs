@@ -6,9 +6,12 @@ fn main() {
     let parser = Parser::new();
     let config = Config::build(&args, &parser);
 
-    if let Ok(config) = config {
-        if let Err(e) = rust_forth::run(config) {
-            println!("{}", e);
+    match config {
+        Ok(config) => {
+            if let Err(e) = rust_forth::run(config) {
+                println!("{}", e);
+            }
         }
+        Err(e) => println!("{}", e),
     }
 }
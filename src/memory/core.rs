@@ -0,0 +1,340 @@
+use crate::cell::{Cell, CellSize, DECIMAL_BASE};
+use crate::errors::Error;
+use crate::forth::forth_errors::ForthError;
+
+/// Default size of the data space, in bytes.
+pub const DEFAULT_DATA_SPACE_SIZE: usize = 8192;
+
+/// # DataSpace struct
+///
+/// This struct represents the data space of the interpreter: a linear, cell-addressed
+/// region of memory where variables, constants' storage and `CREATE`d buffers live.
+///
+/// ## Fields
+///
+/// - `capacity` - Field that represents the maximum number of cells the data space can hold.
+///   The capacity can be defined when creating the data space.
+///   If not provided, the default size is 8 kb.
+///
+/// - `cells` - Field that holds the allotted cells, followed by the transient region, if any.
+///
+/// - `here` - Field that represents the data space pointer (`HERE`): the number of allotted cells.
+///
+/// - `cell_size` - Field that represents the width of the cells, used to compute the capacity.
+///
+/// - `base` - Field that holds the numeric base (`BASE`). It is read and written at `base_address`,
+///   the cell that follows the last one that can be allotted, so that it takes none of them
+///   and `HERE` starts at the first one.
+///
+/// ## Principal Methods
+/// - `new` - Create a new data space with a defined size.
+/// - `with_cell_size` - Set the width of the cells, adjusting the capacity.
+/// - `here` - Get the address of the next free cell.
+/// - `base_address` - Get the address of the cell that holds the numeric base.
+/// - `allot` - Reserve (or release) cells at the end of the data space.
+/// - `comma` - Store a value in the next free cell and reserve it.
+/// - `fetch` - Get the value stored at an address.
+/// - `store` - Store a value at an address.
+/// - `transient` - Store values right after the allotted cells, without reserving them.
+/// - `append_transient` - Store values after the ones of the transient region, without reserving them.
+#[derive(Debug, PartialEq)]
+pub struct DataSpace {
+    capacity: usize,
+    cells: Vec<Cell>,
+    here: usize,
+    cell_size: CellSize,
+    base: Cell,
+}
+
+impl DataSpace {
+    /// Create a new data space with a defined size in bytes.
+    /// If not provided, the default size is 8 kb (4096 cells of 2 bytes each).
+    pub fn new(size: Option<usize>) -> Self {
+        let size = size.unwrap_or(DEFAULT_DATA_SPACE_SIZE);
+        let cell_size = CellSize::default();
+
+        DataSpace {
+            capacity: size / cell_size.bytes(),
+            cells: Vec::new(),
+            here: 0,
+            cell_size,
+            base: DECIMAL_BASE as Cell,
+        }
+    }
+
+    /// Set the width of the cells of the data space.
+    /// The size in bytes is kept, so wider cells fit fewer times in the data space.
+    pub fn with_cell_size(mut self, cell_size: CellSize) -> Self {
+        let size = self.capacity * self.cell_size.bytes();
+        self.capacity = size / cell_size.bytes();
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Get the width of the cells of the data space.
+    pub fn cell_size(&self) -> CellSize {
+        self.cell_size
+    }
+
+    /// Get the capacity of the data space, in cells.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the address of the next free cell.
+    pub fn here(&self) -> usize {
+        self.here
+    }
+
+    /// Get the address of the cell that holds the numeric base (`BASE`),
+    /// the one that follows the last cell that can be allotted.
+    pub fn base_address(&self) -> Cell {
+        self.capacity as Cell
+    }
+
+    /// Reserve `amount` cells at the end of the data space, initialized to zero.
+    /// A negative amount releases the last cells instead.
+    /// The transient region, if any, is discarded.
+    /// Returns the address of the first reserved cell.
+    ///
+    /// If the data space cannot hold the cells, it returns an error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::memory::core::DataSpace;
+    ///
+    /// let mut data_space = DataSpace::new(None);
+    /// let address = data_space.allot(3).unwrap();
+    ///
+    /// assert_eq!(address, 0);
+    /// assert_eq!(data_space.here(), 3);
+    /// ```
+    pub fn allot(&mut self, amount: Cell) -> Result<usize, Error> {
+        let here = self.here();
+        let new_here = here
+            .checked_add_signed(amount as isize)
+            .ok_or(ForthError::InvalidAddress)?;
+        if new_here > self.capacity {
+            return Err(ForthError::DataSpaceOverflow.into());
+        }
+
+        self.cells.truncate(here);
+        self.cells.resize(new_here, 0);
+        self.here = new_here;
+        Ok(here)
+    }
+
+    /// Store a value in the next free cell and reserve it.
+    ///
+    /// If the data space is full, it returns an error.
+    pub fn comma(&mut self, value: Cell) -> Result<(), Error> {
+        let address = self.allot(1)?;
+        self.cells[address] = value;
+        Ok(())
+    }
+
+    /// Get the value stored at an address, or the numeric base at `base_address`.
+    ///
+    /// If the address is outside the allotted cells, it returns an error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::memory::core::DataSpace;
+    ///
+    /// let mut data_space = DataSpace::new(None);
+    /// let address = data_space.allot(1).unwrap();
+    /// data_space.store(address as i64, 42).unwrap();
+    ///
+    /// assert_eq!(data_space.fetch(address as i64), Ok(42));
+    /// assert!(data_space.fetch(1).is_err());
+    /// ```
+    pub fn fetch(&self, address: Cell) -> Result<Cell, Error> {
+        if address == self.base_address() {
+            return Ok(self.base);
+        }
+        let index = self.cell_index(address)?;
+        Ok(self.cells[index])
+    }
+
+    /// Store a value at an address, or the numeric base at `base_address`.
+    ///
+    /// If the address is outside the allotted cells, it returns an error.
+    pub fn store(&mut self, address: Cell, value: Cell) -> Result<(), Error> {
+        if address == self.base_address() {
+            self.base = value;
+            return Ok(());
+        }
+        let index = self.cell_index(address)?;
+        self.cells[index] = value;
+        Ok(())
+    }
+
+    /// Store values in the transient region, right after the allotted cells, without reserving them.
+    /// They can be read until the transient region is replaced or the data space grows,
+    /// like the strings produced by the pictured numeric output.
+    /// Returns the address of the first value.
+    ///
+    /// If the data space cannot hold the values, it returns an error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::memory::core::DataSpace;
+    ///
+    /// let mut data_space = DataSpace::new(None);
+    /// let address = data_space.transient(&[72, 105]).unwrap();
+    ///
+    /// assert_eq!(data_space.fetch(address as i64 + 1), Ok(105));
+    /// assert_eq!(data_space.here(), 0);
+    /// ```
+    pub fn transient(&mut self, values: &[Cell]) -> Result<usize, Error> {
+        if self.here + values.len() > self.capacity {
+            return Err(ForthError::DataSpaceOverflow.into());
+        }
+
+        self.cells.truncate(self.here);
+        self.cells.extend_from_slice(values);
+        Ok(self.here)
+    }
+
+    /// Store values in the transient region after the ones already there, without reserving them,
+    /// so that several of them can be used together, like the strings of `S" abc" S" abd" COMPARE`.
+    /// When they do not fit after the previous ones, the transient region starts over.
+    /// Returns the address of the first value.
+    ///
+    /// If the data space cannot hold the values, it returns an error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::memory::core::DataSpace;
+    ///
+    /// let mut data_space = DataSpace::new(None);
+    /// let first = data_space.append_transient(&[72, 105]).unwrap();
+    /// let second = data_space.append_transient(&[33]).unwrap();
+    ///
+    /// assert_eq!(data_space.fetch(first as i64 + 1), Ok(105));
+    /// assert_eq!(data_space.fetch(second as i64), Ok(33));
+    /// assert_eq!(data_space.here(), 0);
+    /// ```
+    pub fn append_transient(&mut self, values: &[Cell]) -> Result<usize, Error> {
+        if self.cells.len() + values.len() > self.capacity {
+            return self.transient(values);
+        }
+
+        let address = self.cells.len();
+        self.cells.extend_from_slice(values);
+        Ok(address)
+    }
+
+    /// Checks that an address refers to an allotted or transient cell and converts it to an index.
+    fn cell_index(&self, address: Cell) -> Result<usize, Error> {
+        usize::try_from(address)
+            .ok()
+            .filter(|&index| index < self.cells.len())
+            .ok_or(ForthError::InvalidAddress.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_create_data_space_with_defined_size() {
+        let size = 10;
+        let data_space = DataSpace::new(Some(size));
+
+        assert_eq!(data_space.capacity(), size / CellSize::default().bytes());
+        assert_eq!(data_space.here(), 0);
+    }
+
+    #[test]
+    fn wider_cells_reduce_the_capacity_of_the_data_space() {
+        let data_space = DataSpace::new(Some(64)).with_cell_size(CellSize::I32);
+
+        assert_eq!(data_space.capacity(), 16);
+        assert_eq!(data_space.cell_size(), CellSize::I32);
+    }
+
+    #[test]
+    fn allot_moves_the_data_space_pointer() {
+        let mut data_space = DataSpace::new(None);
+
+        let first = data_space.allot(2).unwrap();
+        let second = data_space.allot(3).unwrap();
+        let _ = data_space.allot(-1);
+
+        assert_eq!(first, 0);
+        assert_eq!(second, 2);
+        assert_eq!(data_space.here(), 4);
+    }
+
+    #[test]
+    fn allot_beyond_capacity_should_give_error() {
+        let mut data_space = DataSpace::new(Some(4));
+
+        assert_eq!(
+            data_space.allot(3),
+            Err(ForthError::DataSpaceOverflow.into())
+        );
+        assert_eq!(data_space.allot(-1), Err(ForthError::InvalidAddress.into()));
+    }
+
+    #[test]
+    fn can_store_and_fetch_values() {
+        let mut data_space = DataSpace::new(None);
+        let _ = data_space.comma(7);
+        let _ = data_space.allot(1);
+
+        let _ = data_space.store(1, -3);
+
+        assert_eq!(data_space.fetch(0), Ok(7));
+        assert_eq!(data_space.fetch(1), Ok(-3));
+    }
+
+    #[test]
+    fn accessing_unallotted_addresses_should_give_error() {
+        let mut data_space = DataSpace::new(None);
+        let _ = data_space.allot(1);
+
+        assert_eq!(data_space.fetch(1), Err(ForthError::InvalidAddress.into()));
+        assert_eq!(data_space.fetch(-1), Err(ForthError::InvalidAddress.into()));
+        assert_eq!(
+            data_space.store(5, 1),
+            Err(ForthError::InvalidAddress.into())
+        );
+    }
+
+    #[test]
+    fn the_base_is_kept_outside_the_cells() {
+        let mut data_space = DataSpace::new(Some(4));
+
+        let _ = data_space.store(data_space.base_address(), 16);
+
+        assert_eq!(data_space.base_address(), 2);
+        assert_eq!(data_space.fetch(2), Ok(16));
+        assert_eq!(data_space.here(), 0);
+        assert_eq!(data_space.allot(2), Ok(0));
+        assert_eq!(
+            data_space.allot(1),
+            Err(ForthError::DataSpaceOverflow.into())
+        );
+    }
+
+    #[test]
+    fn the_transient_region_is_discarded_when_the_data_space_grows() {
+        let mut data_space = DataSpace::new(Some(8));
+        let _ = data_space.allot(1);
+
+        let address = data_space.transient(&[1, 2]);
+        assert_eq!(address, Ok(1));
+        assert_eq!(data_space.fetch(2), Ok(2));
+
+        let _ = data_space.allot(1);
+        assert_eq!(data_space.fetch(1), Ok(0));
+        assert_eq!(data_space.fetch(2), Err(ForthError::InvalidAddress.into()));
+        assert_eq!(
+            data_space.transient(&[1, 2, 3]),
+            Err(ForthError::DataSpaceOverflow.into())
+        );
+    }
+}
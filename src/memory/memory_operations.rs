@@ -0,0 +1,125 @@
+use crate::cell::{BINARY_BASE, Cell, DECIMAL_BASE, HEX_BASE};
+use crate::errors::Error;
+use crate::forth::forth_errors::ForthError;
+use crate::memory::core::DataSpace;
+use crate::stack::core::Stack;
+
+/// Constants for memory operations
+pub const FETCH: MemoryOperation = MemoryOperation::Fetch;
+pub const STORE: MemoryOperation = MemoryOperation::Store;
+pub const PLUS_STORE: MemoryOperation = MemoryOperation::PlusStore;
+pub const CHAR_FETCH: MemoryOperation = MemoryOperation::CharFetch;
+pub const CHAR_STORE: MemoryOperation = MemoryOperation::CharStore;
+pub const ALLOT: MemoryOperation = MemoryOperation::Allot;
+pub const COMMA: MemoryOperation = MemoryOperation::Comma;
+pub const HERE: MemoryOperation = MemoryOperation::Here;
+pub const CELLS: MemoryOperation = MemoryOperation::Cells;
+pub const CELL_PLUS: MemoryOperation = MemoryOperation::CellPlus;
+pub const BASE: MemoryOperation = MemoryOperation::Base;
+pub const HEX: MemoryOperation = MemoryOperation::Hex;
+pub const DECIMAL: MemoryOperation = MemoryOperation::Decimal;
+pub const BINARY: MemoryOperation = MemoryOperation::Binary;
+
+/// Mask applied to the values read and written by the character operations.
+pub(crate) const CHAR_MASK: Cell = 0xFF;
+
+/// Enum representing memory operations
+/// such as fetching, storing and reserving cells in the data space.
+/// The operations are defined as follows:
+/// - Fetch: Get the value stored at an address (`@`).
+/// - Store: Store a value at an address (`!`).
+/// - PlusStore: Add a value to the one stored at an address (`+!`).
+/// - CharFetch: Get the character stored at an address (`C@`).
+/// - CharStore: Store a character at an address (`C!`).
+/// - Allot: Reserve a number of cells in the data space (`ALLOT`).
+/// - Comma: Store a value in the next free cell and reserve it (`,`).
+/// - Here: Get the address of the next free cell (`HERE`).
+/// - Cells: Get the size in address units of a number of cells (`CELLS`).
+/// - CellPlus: Get the address of the next cell (`CELL+`).
+/// - Base: Get the address of the cell that holds the numeric base (`BASE`).
+/// - Hex: Set the numeric base to sixteen (`HEX`).
+/// - Decimal: Set the numeric base to ten (`DECIMAL`).
+/// - Binary: Set the numeric base to two (`BINARY`).
+#[derive(Debug, PartialEq, Clone)]
+pub enum MemoryOperation {
+    Fetch,
+    Store,
+    PlusStore,
+    CharFetch,
+    CharStore,
+    Allot,
+    Comma,
+    Here,
+    Cells,
+    CellPlus,
+    Base,
+    Hex,
+    Decimal,
+    Binary,
+}
+
+/// Executes a memory operation on the given data space.
+/// This function takes its operands from the stack, performs the specified operation
+/// on the data space and returns a result indicating success or failure.
+pub fn execute_memory_operation(
+    stack: &mut Stack,
+    data_space: &mut DataSpace,
+    operation: &MemoryOperation,
+) -> Result<(), Error> {
+    match operation {
+        MemoryOperation::Fetch => {
+            let address = stack.drop()?;
+            stack.push(data_space.fetch(address)?)?;
+        }
+        MemoryOperation::Store => {
+            let address = stack.drop()?;
+            let value = stack.drop()?;
+            data_space.store(address, value)?;
+        }
+        MemoryOperation::PlusStore => {
+            let address = stack.drop()?;
+            let value = stack.drop()?;
+            let current = data_space.fetch(address)?;
+            let sum = data_space.cell_size().wrap(current as i128 + value as i128);
+            data_space.store(address, sum)?;
+        }
+        MemoryOperation::CharFetch => {
+            let address = stack.drop()?;
+            stack.push(data_space.fetch(address)? & CHAR_MASK)?;
+        }
+        MemoryOperation::CharStore => {
+            let address = stack.drop()?;
+            let value = stack.drop()?;
+            data_space.store(address, value & CHAR_MASK)?;
+        }
+        MemoryOperation::Allot => {
+            let amount = stack.drop()?;
+            data_space.allot(amount)?;
+        }
+        MemoryOperation::Comma => {
+            let value = stack.drop()?;
+            data_space.comma(value)?;
+        }
+        MemoryOperation::Here => {
+            let here = Cell::try_from(data_space.here()).map_err(|_| ForthError::InvalidAddress)?;
+            stack.push(here)?;
+        }
+        MemoryOperation::Cells => {
+            // The data space is cell-addressed, so a cell occupies a single address unit.
+            stack.top()?;
+        }
+        MemoryOperation::CellPlus => {
+            let address = stack.drop()?;
+            stack.push(address.wrapping_add(1))?;
+        }
+        MemoryOperation::Base => stack.push(data_space.base_address())?,
+        MemoryOperation::Hex => data_space.store(data_space.base_address(), HEX_BASE as Cell)?,
+        MemoryOperation::Decimal => {
+            data_space.store(data_space.base_address(), DECIMAL_BASE as Cell)?
+        }
+        MemoryOperation::Binary => {
+            data_space.store(data_space.base_address(), BINARY_BASE as Cell)?
+        }
+    }
+    Ok(())
+}
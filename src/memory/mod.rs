@@ -0,0 +1,3 @@
+pub mod core;
+pub mod memory_operations;
+pub mod string_operations;
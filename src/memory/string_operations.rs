@@ -0,0 +1,195 @@
+use crate::cell::Cell;
+use crate::errors::Error;
+use crate::forth::boolean_operations::{FORTH_FALSE, FORTH_TRUE};
+use crate::memory::core::DataSpace;
+use crate::memory::memory_operations::CHAR_MASK;
+use crate::stack::core::Stack;
+
+/// Constants for string operations
+pub const COUNT: StringOperation = StringOperation::Count;
+pub const COMPARE: StringOperation = StringOperation::Compare;
+pub const SEARCH: StringOperation = StringOperation::Search;
+pub const SLASH_STRING: StringOperation = StringOperation::SlashString;
+
+/// Constants for string literals
+pub const S_QUOTE: StringKind = StringKind::Plain;
+pub const C_QUOTE: StringKind = StringKind::Counted;
+
+/// Enum representing operations on the strings stored in the data space,
+/// given by the address of their first character and their length.
+/// Characters are stored one per cell.
+/// The operations are defined as follows:
+/// - Count: Get the address and length of the characters of a counted string (`COUNT`).
+/// - Compare: Compare two strings, getting -1, 0 or 1 (`COMPARE`).
+/// - Search: Search a string inside another one (`SEARCH`).
+/// - SlashString: Skip a number of characters of a string (`/STRING`).
+#[derive(Debug, PartialEq, Clone)]
+pub enum StringOperation {
+    Count,
+    Compare,
+    Search,
+    SlashString,
+}
+
+/// Layout in the data space of the strings produced by the string literals.
+/// - Plain: The characters, given by their address and length (`S"`).
+/// - Counted: The length followed by the characters, given by the address of the length (`C"`).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StringKind {
+    Plain,
+    Counted,
+}
+
+/// Executes a string operation on the given data space.
+/// This function takes its operands from the stack, performs the specified operation
+/// and pushes its results, returning a result indicating success or failure.
+pub fn execute_string_operation(
+    stack: &mut Stack,
+    data_space: &DataSpace,
+    operation: &StringOperation,
+) -> Result<(), Error> {
+    match operation {
+        StringOperation::Count => {
+            let address = stack.drop()?;
+            let length = data_space.fetch(address)?;
+            stack.push(address.wrapping_add(1))?;
+            stack.push(length)?;
+        }
+        StringOperation::Compare => {
+            let second_length = stack.drop()?;
+            let second_address = stack.drop()?;
+            let first_length = stack.drop()?;
+            let first_address = stack.drop()?;
+            let first = read_string(data_space, first_address, first_length)?;
+            let second = read_string(data_space, second_address, second_length)?;
+            stack.push(first.cmp(&second) as Cell)?;
+        }
+        StringOperation::Search => {
+            let needle_length = stack.drop()?;
+            let needle_address = stack.drop()?;
+            let length = stack.drop()?;
+            let address = stack.drop()?;
+            let haystack = read_string(data_space, address, length)?;
+            let needle = read_string(data_space, needle_address, needle_length)?;
+            let (offset, flag) = match find_substring(&haystack, &needle) {
+                Some(offset) => (offset as Cell, FORTH_TRUE),
+                None => (0, FORTH_FALSE),
+            };
+            stack.push(address.wrapping_add(offset))?;
+            stack.push(length - offset)?;
+            stack.push(flag)?;
+        }
+        StringOperation::SlashString => {
+            let amount = stack.drop()?;
+            let length = stack.drop()?;
+            let address = stack.drop()?;
+            let cell_size = data_space.cell_size();
+            stack.push(cell_size.wrap(address as i128 + amount as i128))?;
+            stack.push(cell_size.wrap(length as i128 - amount as i128))?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the characters of a string stored in the data space, as bytes like `C@` reads them.
+/// A length that is not positive gives an empty string.
+fn read_string(data_space: &DataSpace, address: Cell, length: Cell) -> Result<Vec<Cell>, Error> {
+    (0..length.max(0))
+        .map(|offset| Ok(data_space.fetch(address.wrapping_add(offset))? & CHAR_MASK))
+        .collect()
+}
+
+/// Finds the offset of the first occurrence of `needle` in `haystack`.
+/// An empty needle is found at the beginning.
+fn find_substring(haystack: &[Cell], needle: &[Cell]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_space_with(text: &str) -> DataSpace {
+        let mut data_space = DataSpace::new(None);
+        for character in text.chars() {
+            data_space.comma(character as Cell).unwrap();
+        }
+        data_space
+    }
+
+    #[test]
+    fn count_gets_the_characters_of_a_counted_string() {
+        let data_space = data_space_with("\u{3}abc");
+        let mut stack = Stack::new(None);
+        stack.push(0).unwrap();
+
+        execute_string_operation(&mut stack, &data_space, &COUNT).unwrap();
+
+        assert_eq!(stack.drop(), Ok(3));
+        assert_eq!(stack.drop(), Ok(1));
+    }
+
+    #[test]
+    fn compare_orders_strings_lexicographically() {
+        let data_space = data_space_with("abcabdab");
+        let mut stack = Stack::new(None);
+
+        for (second_address, second_length, expected_result) in [(3, 3, -1), (0, 3, 0), (6, 2, 1)] {
+            for value in [0, 3, second_address, second_length] {
+                stack.push(value).unwrap();
+            }
+            execute_string_operation(&mut stack, &data_space, &COMPARE).unwrap();
+
+            assert_eq!(stack.drop(), Ok(expected_result));
+        }
+    }
+
+    #[test]
+    fn search_finds_the_first_occurrence() {
+        let data_space = data_space_with("hello worldor");
+        let mut stack = Stack::new(None);
+        for value in [0, 11, 11, 2] {
+            stack.push(value).unwrap();
+        }
+
+        execute_string_operation(&mut stack, &data_space, &SEARCH).unwrap();
+
+        assert_eq!(stack.drop(), Ok(FORTH_TRUE));
+        assert_eq!(stack.drop(), Ok(4));
+        assert_eq!(stack.drop(), Ok(7));
+    }
+
+    #[test]
+    fn search_keeps_the_string_when_not_found() {
+        let data_space = data_space_with("hello");
+        let mut stack = Stack::new(None);
+        for value in [0, 3, 3, 2] {
+            stack.push(value).unwrap();
+        }
+
+        execute_string_operation(&mut stack, &data_space, &SEARCH).unwrap();
+
+        assert_eq!(stack.drop(), Ok(FORTH_FALSE));
+        assert_eq!(stack.drop(), Ok(3));
+        assert_eq!(stack.drop(), Ok(0));
+    }
+
+    #[test]
+    fn slash_string_skips_characters() {
+        let data_space = DataSpace::new(None);
+        let mut stack = Stack::new(None);
+        for value in [10, 5, 2] {
+            stack.push(value).unwrap();
+        }
+
+        execute_string_operation(&mut stack, &data_space, &SLASH_STRING).unwrap();
+
+        assert_eq!(stack.drop(), Ok(3));
+        assert_eq!(stack.drop(), Ok(12));
+    }
+}
@@ -1,11 +1,18 @@
 use super::stack_errors::StackError;
+use crate::cell::{Cell, CellSize};
 use crate::errors::Error;
 
 /// Default capacity of the stack.
 pub const DEFAULT_CAPACITY: usize = 128;
 
-/// Size of each element in the stack.
-const ELEMENT_SIZE: usize = 2; // i16
+/// Kind of a stack, used to report the right errors.
+/// The data stack holds the operands of the words,
+/// while the return stack holds loop parameters and values moved with `>R`.
+#[derive(Debug, PartialEq)]
+pub enum StackKind {
+    Data,
+    Return,
+}
 
 /// # Stack struct
 ///
@@ -14,15 +21,20 @@ const ELEMENT_SIZE: usize = 2; // i16
 /// ## Fields
 ///
 /// - `capacity` - Field that represents the maximum number of elements that the stack can hold.
-///                The capacity can be defined when crating the stack.     
-///                If not provided, the default capacity is 128 kb.
+///   The capacity can be defined when crating the stack.
+///   If not provided, the default capacity is 128 kb.
 ///
 /// - `size` - Field that represents the current number of elements in the stack.
 ///
 /// - `data` - Field that holds the elements of the stack.
 ///
+/// - `kind` - Field that represents whether it is the data stack or the return stack.
+///
+/// - `cell_size` - Field that represents the width of the elements, used to compute the capacity.
+///
 /// ## Principal Methods
 /// - `new` - Create a new instance of the stack with a defined capacity.
+/// - `with_cell_size` - Set the width of the elements, adjusting the capacity.
 /// - `push` - Push an element into the stack.
 /// - `drop` - Remove the last element from the stack.
 /// - `top` - Get the last element from the stack, without removing it.
@@ -30,11 +42,22 @@ const ELEMENT_SIZE: usize = 2; // i16
 /// - `swap` - Swap the last two elements of the stack.
 /// - `over` - Duplicate the second element from the top of the stack.
 /// - `rot` - Rotate the top three elements of the stack.
+/// - `minus_rot` - Rotate the top three elements of the stack in the opposite direction.
+/// - `nip` - Remove the second element from the top of the stack.
+/// - `tuck` - Copy the top element below the second one.
+/// - `pick` - Copy the element at a given depth to the top of the stack.
+/// - `roll` - Move the element at a given depth to the top of the stack.
+/// - `question_dup` - Duplicate the top element if it is not zero.
+/// - `two_dup`, `two_drop`, `two_swap`, `two_over` - Like their single versions, on pairs of elements.
+/// - `get` - Get the element at a given depth from the top, without removing it.
+/// - `truncate` - Remove elements from the top until a given size is reached.
 #[derive(Debug, PartialEq)]
 pub struct Stack {
     capacity: usize,
     size: usize,
-    data: Vec<i16>,
+    data: Vec<Cell>,
+    kind: StackKind,
+    cell_size: CellSize,
 }
 
 impl Stack {
@@ -43,12 +66,59 @@ impl Stack {
     /// In other words, the default capacity is 64 elements (each element occupies 2 bytes).
     pub fn new(capacity: Option<usize>) -> Self {
         let capacity = capacity.unwrap_or(DEFAULT_CAPACITY);
-        let stack_capacity = capacity / ELEMENT_SIZE;
+        let cell_size = CellSize::default();
+        let stack_capacity = capacity / cell_size.bytes();
 
         Stack {
             capacity: stack_capacity,
             size: 0,
             data: Vec::new(),
+            kind: StackKind::Data,
+            cell_size,
+        }
+    }
+
+    /// Set the width of the elements of the stack.
+    /// The capacity in bytes is kept, so wider elements fit fewer times in the stack.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::stack::core::Stack;
+    /// # use rust_forth::cell::CellSize;
+    ///
+    /// let stack = Stack::new(Some(64)).with_cell_size(CellSize::I64);
+    ///
+    /// assert_eq!(stack.capacity(), 8);
+    /// ```
+    pub fn with_cell_size(mut self, cell_size: CellSize) -> Self {
+        let capacity_in_bytes = self.capacity * self.cell_size.bytes();
+        self.capacity = capacity_in_bytes / cell_size.bytes();
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Create a new instance of a return stack with a defined capacity.
+    /// It behaves like the data stack, but reports return stack errors.
+    pub fn new_return_stack(capacity: Option<usize>) -> Self {
+        Stack {
+            kind: StackKind::Return,
+            ..Stack::new(capacity)
+        }
+    }
+
+    /// Error reported when an element is required but the stack is empty.
+    fn underflow(&self) -> Error {
+        match self.kind {
+            StackKind::Data => StackError::Underflow.into(),
+            StackKind::Return => StackError::ReturnStackUnderflow.into(),
+        }
+    }
+
+    /// Error reported when an element is added but the stack is full.
+    fn overflow(&self) -> Error {
+        match self.kind {
+            StackKind::Data => StackError::Overflow.into(),
+            StackKind::Return => StackError::ReturnStackOverflow.into(),
         }
     }
 
@@ -81,10 +151,10 @@ impl Stack {
     ///
     /// assert_eq!(stack.size(), 2);
     /// ```
-    pub fn push(&mut self, element: i16) -> Result<(), Error> {
+    pub fn push(&mut self, element: Cell) -> Result<(), Error> {
         let is_full = self.size >= self.capacity;
         if is_full {
-            return Err(StackError::Overflow.into());
+            return Err(self.overflow());
         }
 
         self.data.push(element);
@@ -108,12 +178,12 @@ impl Stack {
     /// assert_eq!(stack.size(), 1);
     /// assert_eq!(dropped_element, 2);
     /// ```
-    pub fn drop(&mut self) -> Result<i16, Error> {
+    pub fn drop(&mut self) -> Result<Cell, Error> {
         if self.is_empty() {
-            return Err(StackError::Underflow.into());
+            return Err(self.underflow());
         }
 
-        let dropped = self.data.pop().ok_or(StackError::Underflow)?;
+        let dropped = self.data.pop().ok_or_else(|| self.underflow())?;
         self.size -= 1;
         Ok(dropped)
     }
@@ -133,10 +203,10 @@ impl Stack {
     /// assert_eq!(stack.size(), 1);
     /// assert_eq!(top, &1);
     /// ```
-    pub fn top(&self) -> Result<&i16, Error> {
+    pub fn top(&self) -> Result<&Cell, Error> {
         match self.data.last() {
             Some(last) => Ok(last),
-            None => Err(StackError::Underflow.into()),
+            None => Err(self.underflow()),
         }
     }
 
@@ -158,14 +228,14 @@ impl Stack {
     /// ```
     pub fn dup(&mut self) -> Result<(), Error> {
         if self.size >= self.capacity {
-            return Err(StackError::Overflow.into());
+            return Err(self.overflow());
         }
 
         if let Ok(&top) = self.top() {
             self.push(top)?;
             Ok(())
         } else {
-            Err(StackError::Underflow.into())
+            Err(self.underflow())
         }
     }
 
@@ -186,7 +256,7 @@ impl Stack {
     /// ```
     pub fn swap(&mut self) -> Result<(), Error> {
         if self.size < 2 {
-            return Err(StackError::Underflow.into());
+            return Err(self.underflow());
         }
 
         let last = self.drop()?;
@@ -215,9 +285,9 @@ impl Stack {
     /// ```
     pub fn over(&mut self) -> Result<(), Error> {
         if self.size < 2 {
-            return Err(StackError::Underflow.into());
+            return Err(self.underflow());
         } else if self.size >= self.capacity {
-            return Err(StackError::Overflow.into());
+            return Err(self.overflow());
         }
 
         let last = self.drop()?;
@@ -245,7 +315,7 @@ impl Stack {
     /// ```
     pub fn rot(&mut self) -> Result<(), Error> {
         if self.size < 3 {
-            return Err(StackError::Underflow.into());
+            return Err(self.underflow());
         }
 
         let mut tops = Vec::new();
@@ -264,8 +334,242 @@ impl Stack {
         Ok(())
     }
 
+    /// Check that the stack holds at least `elements` elements.
+    fn require(&self, elements: usize) -> Result<(), Error> {
+        if self.size < elements {
+            return Err(self.underflow());
+        }
+        Ok(())
+    }
+
+    /// Check that `elements` more elements fit in the stack.
+    fn reserve(&self, elements: usize) -> Result<(), Error> {
+        if self.size + elements > self.capacity {
+            return Err(self.overflow());
+        }
+        Ok(())
+    }
+
+    /// Read the depth operand of `pick` and `roll` from the top of the stack, without removing it.
+    /// Checks that there is an element at that depth below the operand.
+    fn depth_operand(&self) -> Result<usize, Error> {
+        let depth = *self.top()?;
+        usize::try_from(depth)
+            .ok()
+            .filter(|&depth| depth + 1 < self.size)
+            .ok_or_else(|| self.underflow())
+    }
+
+    /// Rotate the top three elements of the stack in the opposite direction of `rot` (`-ROT`).
+    ///
+    /// If the stack holds less than three elements, it returns an underflow error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::stack::core::Stack;
+    ///
+    /// let mut stack = Stack::new(None);
+    /// stack.push(1).unwrap();
+    /// stack.push(2).unwrap();
+    /// stack.push(3).unwrap();
+    /// stack.minus_rot().unwrap();
+    ///
+    /// assert_eq!(stack.get_stack_content(), &[3, 1, 2]);
+    /// ```
+    pub fn minus_rot(&mut self) -> Result<(), Error> {
+        self.require(3)?;
+        let top = self.size - 1;
+        self.data[top - 2..].rotate_right(1);
+        Ok(())
+    }
+
+    /// Remove the second element from the top of the stack (`NIP`).
+    ///
+    /// If the stack holds less than two elements, it returns an underflow error.
+    pub fn nip(&mut self) -> Result<(), Error> {
+        self.require(2)?;
+        self.data.remove(self.size - 2);
+        self.size -= 1;
+        Ok(())
+    }
+
+    /// Copy the top element below the second one (`TUCK`).
+    ///
+    /// If the stack holds less than two elements, it returns an underflow error.
+    /// If the stack is full, it returns an overflow error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::stack::core::Stack;
+    ///
+    /// let mut stack = Stack::new(None);
+    /// stack.push(1).unwrap();
+    /// stack.push(2).unwrap();
+    /// stack.tuck().unwrap();
+    ///
+    /// assert_eq!(stack.get_stack_content(), &[2, 1, 2]);
+    /// ```
+    pub fn tuck(&mut self) -> Result<(), Error> {
+        self.require(2)?;
+        self.reserve(1)?;
+        let top = *self.top()?;
+        self.data.insert(self.size - 2, top);
+        self.size += 1;
+        Ok(())
+    }
+
+    /// Take a depth from the top of the stack and copy the element at that depth
+    /// to the top (`PICK`). A depth of 0 copies the top element, like `dup`.
+    ///
+    /// If the stack does not hold an element at that depth, it returns an underflow error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::stack::core::Stack;
+    ///
+    /// let mut stack = Stack::new(None);
+    /// stack.push(1).unwrap();
+    /// stack.push(2).unwrap();
+    /// stack.push(3).unwrap();
+    /// stack.push(2).unwrap();
+    /// stack.pick().unwrap();
+    ///
+    /// assert_eq!(stack.get_stack_content(), &[1, 2, 3, 1]);
+    /// ```
+    pub fn pick(&mut self) -> Result<(), Error> {
+        let depth = self.depth_operand()?;
+        let picked = *self.get(depth + 1)?;
+        self.drop()?;
+        self.push(picked)
+    }
+
+    /// Take a depth from the top of the stack and move the element at that depth
+    /// to the top (`ROLL`). A depth of 1 is like `swap` and a depth of 2 is like `rot`.
+    ///
+    /// If the stack does not hold an element at that depth, it returns an underflow error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::stack::core::Stack;
+    ///
+    /// let mut stack = Stack::new(None);
+    /// stack.push(1).unwrap();
+    /// stack.push(2).unwrap();
+    /// stack.push(3).unwrap();
+    /// stack.push(2).unwrap();
+    /// stack.roll().unwrap();
+    ///
+    /// assert_eq!(stack.get_stack_content(), &[2, 3, 1]);
+    /// ```
+    pub fn roll(&mut self) -> Result<(), Error> {
+        let depth = self.depth_operand()?;
+        self.drop()?;
+        let index = self.size - 1 - depth;
+        let rolled = self.data.remove(index);
+        self.data.push(rolled);
+        Ok(())
+    }
+
+    /// Duplicate the top element of the stack if it is not zero (`?DUP`).
+    ///
+    /// If the stack is empty, it returns an underflow error.
+    /// If the stack is full and the element must be duplicated, it returns an overflow error.
+    pub fn question_dup(&mut self) -> Result<(), Error> {
+        if *self.top()? != 0 {
+            self.dup()?;
+        }
+        Ok(())
+    }
+
+    /// Duplicate the top two elements of the stack (`2DUP`).
+    ///
+    /// If the stack holds less than two elements, it returns an underflow error.
+    /// If the stack cannot hold two more elements, it returns an overflow error.
+    pub fn two_dup(&mut self) -> Result<(), Error> {
+        self.require(2)?;
+        self.reserve(2)?;
+        self.data.extend_from_within(self.size - 2..);
+        self.size += 2;
+        Ok(())
+    }
+
+    /// Remove the top two elements of the stack (`2DROP`).
+    ///
+    /// If the stack holds less than two elements, it returns an underflow error.
+    pub fn two_drop(&mut self) -> Result<(), Error> {
+        self.require(2)?;
+        self.truncate(self.size - 2);
+        Ok(())
+    }
+
+    /// Swap the top two pairs of elements of the stack (`2SWAP`).
+    ///
+    /// If the stack holds less than four elements, it returns an underflow error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::stack::core::Stack;
+    ///
+    /// let mut stack = Stack::new(None);
+    /// for element in 1..=4 {
+    ///     stack.push(element).unwrap();
+    /// }
+    /// stack.two_swap().unwrap();
+    ///
+    /// assert_eq!(stack.get_stack_content(), &[3, 4, 1, 2]);
+    /// ```
+    pub fn two_swap(&mut self) -> Result<(), Error> {
+        self.require(4)?;
+        let start = self.size - 4;
+        self.data[start..].rotate_left(2);
+        Ok(())
+    }
+
+    /// Copy the second pair of elements from the top of the stack (`2OVER`).
+    ///
+    /// If the stack holds less than four elements, it returns an underflow error.
+    /// If the stack cannot hold two more elements, it returns an overflow error.
+    pub fn two_over(&mut self) -> Result<(), Error> {
+        self.require(4)?;
+        self.reserve(2)?;
+        let start = self.size - 4;
+        self.data.extend_from_within(start..start + 2);
+        self.size += 2;
+        Ok(())
+    }
+
+    /// Get the element at the given depth from the top of the stack, without removing it.
+    /// A depth of 0 refers to the top of the stack.
+    ///
+    /// If the stack does not hold enough elements, it returns an underflow error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use rust_forth::stack::core::Stack;
+    ///
+    /// let mut stack = Stack::new(None);
+    /// stack.push(1).unwrap();
+    /// stack.push(2).unwrap();
+    ///
+    /// assert_eq!(stack.get(0), Ok(&2));
+    /// assert_eq!(stack.get(1), Ok(&1));
+    /// assert!(stack.get(2).is_err());
+    /// ```
+    pub fn get(&self, depth: usize) -> Result<&Cell, Error> {
+        self.size
+            .checked_sub(depth + 1)
+            .and_then(|index| self.data.get(index))
+            .ok_or_else(|| self.underflow())
+    }
+
+    /// Remove elements from the top of the stack until it holds at most `size` elements.
+    pub fn truncate(&mut self, size: usize) {
+        self.data.truncate(size);
+        self.size = self.data.len();
+    }
+
     /// Get the content of the stack.
-    pub fn get_stack_content(&self) -> &Vec<i16> {
+    pub fn get_stack_content(&self) -> &Vec<Cell> {
         &self.data
     }
 }
@@ -319,7 +623,7 @@ mod tests {
     fn can_drop_elements_from_stack_correctly() {
         let mut stack = Stack::new(None);
         let elements = vec![1, 2, -3];
-        let mut droped_elements: Vec<Result<i16, StackError>> = Vec::new();
+        let mut droped_elements: Vec<Result<Cell, StackError>> = Vec::new();
 
         for element in &elements {
             let _ = stack.push(*element);
@@ -339,7 +643,7 @@ mod tests {
     fn can_drop_elements_from_stack_until_empty() {
         let mut stack = Stack::new(None);
         let elements = vec![1, 2, -3];
-        let mut droped_elements: Vec<Result<i16, &str>> = Vec::new();
+        let mut droped_elements: Vec<Result<Cell, &str>> = Vec::new();
 
         for element in &elements {
             let _ = stack.push(*element);
@@ -369,7 +673,7 @@ mod tests {
         let stack = Stack::new(Some(capacity));
         // stack capacity expected:
         // capacity / number of bytes an element occupies
-        let expected_capacty = capacity / ELEMENT_SIZE;
+        let expected_capacty = capacity / CellSize::default().bytes();
 
         assert_eq!(stack.capacity(), expected_capacty);
     }
@@ -377,11 +681,20 @@ mod tests {
     #[test]
     fn can_create_stack_with_default_capacity() {
         let stack = Stack::new(None);
-        let expected_capacty = DEFAULT_CAPACITY / ELEMENT_SIZE;
+        let expected_capacty = DEFAULT_CAPACITY / CellSize::default().bytes();
 
         assert_eq!(stack.capacity(), expected_capacty);
     }
 
+    #[test]
+    fn wider_elements_reduce_the_capacity_of_the_stack() {
+        let capacity = 64;
+
+        let stack = Stack::new(Some(capacity)).with_cell_size(CellSize::I32);
+
+        assert_eq!(stack.capacity(), capacity / CellSize::I32.bytes());
+    }
+
     #[test]
     fn attempting_to_load_stack_beyond_capacity_should_give_error() {
         let capacity = 2;
@@ -583,4 +896,123 @@ mod tests {
 
         assert_eq!(dropped, [1, 3, 2]);
     }
+
+    #[test]
+    fn return_stack_reports_its_own_errors() {
+        let mut return_stack = Stack::new_return_stack(Some(2));
+
+        assert_eq!(
+            return_stack.drop(),
+            Err(StackError::ReturnStackUnderflow.into())
+        );
+        let _ = return_stack.push(1);
+        assert_eq!(
+            return_stack.push(2),
+            Err(StackError::ReturnStackOverflow.into())
+        );
+    }
+
+    #[test]
+    fn can_get_elements_by_depth() {
+        let mut stack = Stack::new(None);
+        let elements = vec![1, 2, 3];
+
+        for element in &elements {
+            let _ = stack.push(*element);
+        }
+
+        assert_eq!(stack.get(0), Ok(&3));
+        assert_eq!(stack.get(2), Ok(&1));
+        assert_eq!(stack.get(3), Err(StackError::Underflow.into()));
+    }
+
+    #[test]
+    fn can_truncate_the_stack() {
+        let mut stack = Stack::new(None);
+        let elements = vec![1, 2, 3];
+
+        for element in &elements {
+            let _ = stack.push(*element);
+        }
+        stack.truncate(1);
+
+        assert_eq!(stack.size(), 1);
+        assert_eq!(stack.get_stack_content(), &[1]);
+    }
+
+    #[test]
+    fn can_pick_elements_by_depth() {
+        let mut stack = Stack::new(None);
+        for element in [1, 2, 3, 1] {
+            let _ = stack.push(element);
+        }
+
+        assert_eq!(stack.pick(), Ok(()));
+        assert_eq!(stack.get_stack_content(), &[1, 2, 3, 2]);
+    }
+
+    #[test]
+    fn picking_beyond_the_stack_should_give_error() {
+        let mut stack = Stack::new(None);
+        for element in [1, 2, 2] {
+            let _ = stack.push(element);
+        }
+
+        assert_eq!(stack.pick(), Err(StackError::Underflow.into()));
+        let _ = stack.drop();
+        let _ = stack.push(-1);
+        assert_eq!(stack.pick(), Err(StackError::Underflow.into()));
+        assert_eq!(stack.get_stack_content(), &[1, 2, -1]);
+    }
+
+    #[test]
+    fn can_roll_elements_by_depth() {
+        let mut stack = Stack::new(None);
+        for element in [1, 2, 3, 2] {
+            let _ = stack.push(element);
+        }
+
+        assert_eq!(stack.roll(), Ok(()));
+        assert_eq!(stack.get_stack_content(), &[2, 3, 1]);
+        let _ = stack.push(3);
+        assert_eq!(stack.roll(), Err(StackError::Underflow.into()));
+    }
+
+    #[test]
+    fn can_manipulate_pairs_of_elements() {
+        let mut stack = Stack::new(None);
+        for element in [1, 2, 3, 4] {
+            let _ = stack.push(element);
+        }
+
+        assert_eq!(stack.two_swap(), Ok(()));
+        assert_eq!(stack.get_stack_content(), &[3, 4, 1, 2]);
+        assert_eq!(stack.two_over(), Ok(()));
+        assert_eq!(stack.get_stack_content(), &[3, 4, 1, 2, 3, 4]);
+        assert_eq!(stack.two_drop(), Ok(()));
+        assert_eq!(stack.two_dup(), Ok(()));
+        assert_eq!(stack.get_stack_content(), &[3, 4, 1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn question_dup_only_duplicates_non_zero_elements() {
+        let mut stack = Stack::new(None);
+        let _ = stack.push(0);
+        let _ = stack.question_dup();
+        let _ = stack.push(5);
+        let _ = stack.question_dup();
+
+        assert_eq!(stack.get_stack_content(), &[0, 5, 5]);
+    }
+
+    #[test]
+    fn pair_words_with_a_single_element_should_give_error() {
+        let mut stack = Stack::new(None);
+        let _ = stack.push(1);
+
+        assert_eq!(stack.nip(), Err(StackError::Underflow.into()));
+        assert_eq!(stack.two_drop(), Err(StackError::Underflow.into()));
+        assert_eq!(stack.two_dup(), Err(StackError::Underflow.into()));
+        assert_eq!(stack.get_stack_content(), &[1]);
+    }
 }
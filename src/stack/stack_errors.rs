@@ -4,6 +4,8 @@ use std::fmt;
 pub enum StackError {
     Underflow,
     Overflow,
+    ReturnStackUnderflow,
+    ReturnStackOverflow,
 }
 
 impl fmt::Display for StackError {
@@ -11,6 +13,8 @@ impl fmt::Display for StackError {
         match *self {
             StackError::Underflow => write!(f, "stack-underflow"),
             StackError::Overflow => write!(f, "stack-overflow"),
+            StackError::ReturnStackUnderflow => write!(f, "return-stack-underflow"),
+            StackError::ReturnStackOverflow => write!(f, "return-stack-overflow"),
         }
     }
 }
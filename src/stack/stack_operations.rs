@@ -1,5 +1,7 @@
+use crate::cell::Cell;
 use crate::errors::Error;
 use crate::stack::core::Stack;
+use crate::stack::stack_errors::StackError;
 
 /// Constants for stack operations
 pub const DUP: StackOperation = StackOperation::Dup;
@@ -7,6 +9,24 @@ pub const DROP: StackOperation = StackOperation::Drop;
 pub const SWAP: StackOperation = StackOperation::Swap;
 pub const OVER: StackOperation = StackOperation::Over;
 pub const ROT: StackOperation = StackOperation::Rot;
+pub const MINUS_ROT: StackOperation = StackOperation::MinusRot;
+pub const NIP: StackOperation = StackOperation::Nip;
+pub const TUCK: StackOperation = StackOperation::Tuck;
+pub const PICK: StackOperation = StackOperation::Pick;
+pub const ROLL: StackOperation = StackOperation::Roll;
+pub const QUESTION_DUP: StackOperation = StackOperation::QuestionDup;
+pub const TWO_DUP: StackOperation = StackOperation::TwoDup;
+pub const TWO_DROP: StackOperation = StackOperation::TwoDrop;
+pub const TWO_SWAP: StackOperation = StackOperation::TwoSwap;
+pub const TWO_OVER: StackOperation = StackOperation::TwoOver;
+pub const DEPTH: StackOperation = StackOperation::Depth;
+
+/// Constants for return stack operations
+pub const TO_R: StackOperation = StackOperation::ToR;
+pub const R_FROM: StackOperation = StackOperation::RFrom;
+pub const R_FETCH: StackOperation = StackOperation::RFetch;
+pub const TWO_TO_R: StackOperation = StackOperation::TwoToR;
+pub const TWO_R_FROM: StackOperation = StackOperation::TwoRFrom;
 
 /// Enum representing stack operations
 /// such as duplication, dropping, swapping, etc.
@@ -17,27 +37,95 @@ pub const ROT: StackOperation = StackOperation::Rot;
 /// - Swap: Swap the top two elements of the stack.
 /// - Over: Copy the second element from the top of the stack.
 /// - Rot: Rotate the top three elements of the stack.
-#[derive(Debug, PartialEq)]
+/// - MinusRot: Rotate the top three elements of the stack the other way (`-ROT`).
+/// - Nip: Remove the second element from the top of the stack.
+/// - Tuck: Copy the top element below the second one.
+/// - Pick: Copy the element at the depth taken from the stack to the top.
+/// - Roll: Move the element at the depth taken from the stack to the top.
+/// - QuestionDup: Duplicate the top element if it is not zero (`?DUP`).
+/// - TwoDup: Duplicate the top two elements of the stack (`2DUP`).
+/// - TwoDrop: Remove the top two elements of the stack (`2DROP`).
+/// - TwoSwap: Swap the top two pairs of elements of the stack (`2SWAP`).
+/// - TwoOver: Copy the second pair of elements from the top of the stack (`2OVER`).
+/// - Depth: Push the number of elements of the stack.
+/// - ToR: Move the top element of the stack to the return stack (`>R`).
+/// - RFrom: Move the top element of the return stack to the stack (`R>`).
+/// - RFetch: Copy the top element of the return stack to the stack (`R@`).
+/// - TwoToR: Move the top two elements of the stack to the return stack (`2>R`).
+/// - TwoRFrom: Move the top two elements of the return stack to the stack (`2R>`).
+#[derive(Debug, PartialEq, Clone)]
 pub enum StackOperation {
     Dup,
     Drop,
     Swap,
     Over,
     Rot,
+    MinusRot,
+    Nip,
+    Tuck,
+    Pick,
+    Roll,
+    QuestionDup,
+    TwoDup,
+    TwoDrop,
+    TwoSwap,
+    TwoOver,
+    Depth,
+    ToR,
+    RFrom,
+    RFetch,
+    TwoToR,
+    TwoRFrom,
 }
 
 /// Executes a stack operation on the given stack.
 /// This function performs the specified operation
 /// on the stack and returns a result indicating success or failure.
-pub fn execute_stack_operation(stack: &mut Stack, operation: &StackOperation) -> Result<(), Error> {
+/// Operations that transfer elements use the return stack as well.
+pub fn execute_stack_operation(
+    stack: &mut Stack,
+    return_stack: &mut Stack,
+    operation: &StackOperation,
+) -> Result<(), Error> {
     match operation {
         StackOperation::Dup => stack.dup()?,
         StackOperation::Swap => stack.swap()?,
         StackOperation::Over => stack.over()?,
         StackOperation::Rot => stack.rot()?,
+        StackOperation::MinusRot => stack.minus_rot()?,
+        StackOperation::Nip => stack.nip()?,
+        StackOperation::Tuck => stack.tuck()?,
+        StackOperation::Pick => stack.pick()?,
+        StackOperation::Roll => stack.roll()?,
+        StackOperation::QuestionDup => stack.question_dup()?,
+        StackOperation::TwoDup => stack.two_dup()?,
+        StackOperation::TwoDrop => stack.two_drop()?,
+        StackOperation::TwoSwap => stack.two_swap()?,
+        StackOperation::TwoOver => stack.two_over()?,
+        StackOperation::Depth => {
+            let depth = Cell::try_from(stack.size()).map_err(|_| StackError::Overflow)?;
+            stack.push(depth)?
+        }
         StackOperation::Drop => {
             stack.drop()?;
         }
+        StackOperation::ToR => return_stack.push(stack.drop()?)?,
+        StackOperation::RFrom => stack.push(return_stack.drop()?)?,
+        StackOperation::RFetch => stack.push(*return_stack.top()?)?,
+        StackOperation::TwoToR => {
+            stack.get(1)?;
+            let second = stack.drop()?;
+            let first = stack.drop()?;
+            return_stack.push(first)?;
+            return_stack.push(second)?;
+        }
+        StackOperation::TwoRFrom => {
+            return_stack.get(1)?;
+            let second = return_stack.drop()?;
+            let first = return_stack.drop()?;
+            stack.push(first)?;
+            stack.push(second)?;
+        }
     }
     Ok(())
 }
@@ -1,13 +1,15 @@
 use rust_forth::{
     Forth, Instruction,
+    cell::CellSize,
     forth::{
         boolean_operations::{FORTH_TRUE, GREATER_THAN, LESS_THAN},
         definition_type::{DefinitionType, ELSE, IF, THEN},
-        forth_errors::ForthError,
-        word::WordType,
+        forth_errors::{ForthError, UnknownToken},
+        output_instructions::OutputMode,
+        word::{CaseSensitivity, WordType},
         word_data::WordData,
     },
-    stack::stack_operations::DROP,
+    stack::{stack_errors::StackError, stack_operations::DROP},
 };
 use std::io::Sink;
 
@@ -133,7 +135,7 @@ fn cannot_execute_unknown_word() {
 
     let result = forth.process_instructions(unknown_word);
 
-    assert_eq!(result, Err(ForthError::UnknownWord.into()));
+    assert_eq!(result, Err(ForthError::UnknownWord(None).into()));
 }
 
 #[test]
@@ -190,3 +192,512 @@ fn a_word_can_be_defined_on_multiple_lines() {
 
     assert_eq!(result.unwrap(), &expected_result);
 }
+
+#[test]
+fn can_execute_a_word_with_nested_counted_loops() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+    let input = ": table 4 1 do cr 4 1 do i j * . loop loop ;".to_string();
+
+    let definition = forth.parse_instructions(input);
+    let _ = forth.process_instructions(definition);
+    let instructions = forth.parse_instructions("table".to_string());
+    let _ = forth.process_instructions(instructions);
+
+    let result = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+
+    assert_eq!(result, "\n1 2 3 \n2 4 6 \n3 6 9 ");
+    assert!(forth.is_stack_empty());
+}
+
+#[test]
+fn cannot_define_a_word_with_an_unbalanced_loop() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let input = ": countdown begin dup 1 - dup 0 = ;".to_string();
+
+    let instructions = forth.parse_instructions(input);
+    let result = forth.process_instructions(instructions);
+
+    assert_eq!(result, Err(ForthError::UnbalancedControlStructure.into()));
+    assert!(!forth.is_word_defined(&WordType::UserDefined("countdown".to_string())));
+}
+
+#[test]
+fn can_build_a_table_with_create_allot_and_comma() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let input = "create table 10 , 20 , 30 , table cell+ @ table 2 cells + @".to_string();
+
+    let instructions = forth.parse_instructions(input);
+    let result = forth.process_instructions(instructions);
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(forth.get_stack_content(), &vec![20, 30]);
+}
+
+#[test]
+fn can_use_variables_and_values_inside_definitions() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let setup = "variable counter 0 value last".to_string();
+    let definition = ": bump counter @ 1 + dup counter ! to last ;".to_string();
+
+    let instructions = forth.parse_instructions(setup);
+    let _ = forth.process_instructions(instructions);
+    let instructions = forth.parse_instructions(definition);
+    let _ = forth.process_instructions(instructions);
+    let instructions = forth.parse_instructions("bump bump last counter @".to_string());
+    let result = forth.process_instructions(instructions);
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(forth.get_stack_content(), &vec![2, 2]);
+}
+
+#[test]
+fn cannot_fetch_from_an_invalid_address() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let instructions = forth.parse_instructions("-2 @".to_string());
+
+    let result = forth.process_instructions(instructions);
+
+    assert_eq!(result, Err(ForthError::InvalidAddress.into()));
+}
+
+#[test]
+fn can_execute_recursive_words() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let factorial = ": fact dup 1 > if dup 1 - recurse * then ;".to_string();
+    let fibonacci = ": fib dup 2 < if exit then dup 1 - fib swap 2 - fib + ;".to_string();
+
+    for input in [factorial, fibonacci, "5 fact 10 fib".to_string()] {
+        let instructions = forth.parse_instructions(input);
+        let _ = forth.process_instructions(instructions);
+    }
+
+    assert_eq!(forth.get_stack_content(), &vec![120, 55]);
+}
+
+#[test]
+fn redefined_word_refers_to_its_previous_definition() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+
+    for input in [": foo 1 ;", ": foo foo 2 ;", "foo"] {
+        let instructions = forth.parse_instructions(input.to_string());
+        let _ = forth.process_instructions(instructions);
+    }
+
+    assert_eq!(forth.get_stack_content(), &vec![1, 2]);
+}
+
+#[test]
+fn runaway_recursion_raises_an_error_with_the_default_depth() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let definition = forth.parse_instructions(": forever 1 + forever ;".to_string());
+    let _ = forth.process_instructions(definition);
+
+    let instructions = forth.parse_instructions("0 forever".to_string());
+    let result = forth.process_instructions(instructions);
+
+    assert_eq!(result, Err(ForthError::MaxCallDepthExceeded.into()));
+}
+
+#[test]
+fn recursion_up_to_the_default_depth_does_not_overflow() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let definition = ": down dup 0 > if 1 - begin recurse 1 until then ;".to_string();
+    let instructions = forth.parse_instructions(definition);
+    let _ = forth.process_instructions(instructions);
+
+    let instructions = forth.parse_instructions("255 down".to_string());
+    let result = forth.process_instructions(instructions);
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(forth.get_stack_content(), &vec![0]);
+}
+
+#[test]
+fn strict_mode_rejects_a_definition_with_a_typo() {
+    let forth: Forth<Sink> = Forth::new(None, None).with_strict_mode(true);
+
+    let result = forth.try_parse_instructions(": square dup * ; : cube dup square * ;".to_string());
+    assert!(result.is_ok());
+
+    let result = forth.try_parse_instructions(": cube dup sqare * ;".to_string());
+
+    assert_eq!(
+        result,
+        Err(ForthError::UnknownWord(Some(UnknownToken::new("sqare", 12))).into())
+    );
+}
+
+#[test]
+fn errors_are_located_inside_the_word_that_raised_them() {
+    let mut forth: Forth<Sink> = Forth::new(None, None).with_source_name("input.fth");
+    let (definition, spans) = forth
+        .parse_with_spans(": double\n  dup + ;".to_string(), 11)
+        .unwrap();
+    let _ = forth.process_instructions_with_spans(definition, spans);
+
+    let (instructions, spans) = forth
+        .parse_with_spans("5 . double".to_string(), 14)
+        .unwrap();
+    let error = forth
+        .process_instructions_with_spans(instructions, spans)
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "input.fth:12:3: stack-underflow in 'double'"
+    );
+    assert_eq!(error.into_terse(), StackError::Underflow.into());
+}
+
+#[test]
+fn errors_inside_nested_words_have_a_traceback() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let code = ": square * ;\n: hypot square swap square + ;";
+    for (line_number, line) in code.lines().enumerate() {
+        let (instructions, spans) = forth
+            .parse_with_spans(line.to_string(), line_number + 1)
+            .unwrap();
+        let _ = forth.process_instructions_with_spans(instructions, spans);
+    }
+
+    let (instructions, spans) = forth.parse_with_spans("3 hypot".to_string(), 7).unwrap();
+    let error = forth
+        .process_instructions_with_spans(instructions, spans)
+        .unwrap_err();
+    assert_eq!(error.traceback(), None);
+
+    let error = forth.attach_traceback(error);
+    let traceback = error.traceback().unwrap();
+    assert_eq!(traceback.words, vec!["square", "hypot"]);
+    assert_eq!(traceback.line, Some(7));
+    assert_eq!(
+        error.to_string(),
+        "1:10: stack-underflow in 'square'\n  in square\n  in hypot\n  at line 7"
+    );
+}
+
+#[test]
+fn sixty_four_bit_cells_can_hold_large_results() {
+    let mut forth: Forth<Sink> = Forth::new(Some(1024), None).with_cell_size(CellSize::I64);
+    let definition = ": fact dup 1 > if dup 1 - recurse * then ;".to_string();
+    let instructions = forth.parse_instructions(definition);
+    let _ = forth.process_instructions(instructions);
+
+    let instructions = forth.parse_instructions("20 fact".to_string());
+    let result = forth.process_instructions(instructions);
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(forth.get_stack_content(), &vec![2432902008176640000]);
+}
+
+#[test]
+fn numbers_are_read_and_printed_in_the_current_base() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+
+    for input in [
+        "hex ff . 10",
+        "decimal dup . binary 101 .",
+        "base @ decimal .",
+    ] {
+        let instructions = forth.parse_instructions(input.to_string());
+        let _ = forth.process_instructions(instructions);
+    }
+
+    let result = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(result, "FF 16 101 2 ");
+    assert_eq!(forth.get_stack_content(), &vec![16]);
+}
+
+#[test]
+fn pictured_output_can_format_numbers_of_every_cell_size() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output)).with_cell_size(CellSize::I64);
+    let input = "-1 -1 <# #s #> type cr -1 0 hex <# #s #> type".to_string();
+
+    let instructions = forth.parse_instructions(input);
+    let _ = forth.process_instructions(instructions);
+
+    let result = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(result, format!("{}\nFFFFFFFFFFFFFFFF", u128::MAX));
+    assert!(forth.is_stack_empty());
+}
+
+#[test]
+fn user_words_take_precedence_over_the_pictured_output_words() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+
+    for line in [
+        ": sign 7 ;",
+        ": hold 5 ;",
+        ": type 42 emit ;",
+        "sign hold type",
+    ] {
+        let instructions = forth.parse_instructions(line.to_string());
+        let result = forth.process_instructions(instructions);
+        assert_eq!(result, Ok(()));
+    }
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(output, "* ");
+    assert_eq!(forth.get_stack_content(), &vec![7, 5]);
+}
+
+#[test]
+fn dump_writes_the_data_space_through_the_writer() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+    let input = "create bytes 10 , 255 , hex bytes 2 dump decimal".to_string();
+
+    let instructions = forth.parse_instructions(input);
+    let result = forth.process_instructions(instructions);
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(result, Ok(()));
+    assert_eq!(output, "0: A FF\n");
+}
+
+#[test]
+fn standard_output_mode_prints_no_spurious_spaces() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output)).with_output_mode(OutputMode::Standard);
+    let input = r#"72 emit 105 emit ." there" bl emit 2 spaces 42 . space 7 0 <# #s #> type"#;
+
+    let instructions = forth.parse_instructions(input.to_string());
+    let result = forth.process_instructions(instructions);
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(result, Ok(()));
+    assert_eq!(output, "Hithere   42  7");
+}
+
+#[test]
+fn user_words_take_precedence_over_the_spacing_words() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output)).with_output_mode(OutputMode::Standard);
+
+    for line in [
+        ": bl 5 ;",
+        ": space 42 emit ;",
+        ": spaces drop ;",
+        "bl space 3 spaces",
+    ] {
+        let instructions = forth.parse_instructions(line.to_string());
+        let result = forth.process_instructions(instructions);
+        assert_eq!(result, Ok(()));
+    }
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(output, "*");
+    assert_eq!(forth.get_stack_content(), &vec![5]);
+}
+
+#[test]
+fn control_structures_can_be_interpreted_outside_definitions() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+    let input = "0 if 10 else 20 then . 3 0 do i . loop -1 if 1 if 7 . then then".to_string();
+
+    let instructions = forth.parse_instructions(input);
+    let result = forth.process_instructions(instructions);
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(result, Ok(()));
+    assert_eq!(output, "20 0 1 2 7 ");
+    assert!(forth.is_stack_empty());
+}
+
+#[test]
+fn compile_only_words_are_rejected_outside_definitions() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+
+    for input in ["then", "i", "leave", "exit", "1 if recurse then"] {
+        let instructions = forth.parse_instructions(input.to_string());
+        let result = forth.process_instructions(instructions);
+
+        assert_eq!(result, Err(ForthError::CompileOnlyWord.into()), "{input}");
+    }
+}
+
+#[test]
+fn errors_inside_interpreted_control_structures_are_located_at_the_token() {
+    let mut forth: Forth<Sink> = Forth::new(None, None).with_source_name("input.fth");
+    let (instructions, spans) = forth
+        .parse_with_spans("1 if drop drop then".to_string(), 3)
+        .unwrap();
+
+    let result = forth.process_instructions_with_spans(instructions, spans);
+
+    let error = result.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "input.fth:3:6: stack-underflow in 'drop'"
+    );
+}
+
+#[test]
+fn comments_are_ignored_and_the_stack_effect_is_kept() {
+    let mut forth: Forth<Sink> = Forth::new(None, None).with_strict_mode(true);
+    let definition = ": double ( n -- 2n )\n  dup + ; \\ doubles a number".to_string();
+    let input = "3 double ( 6 )".to_string();
+
+    let (instructions, spans) = forth.parse_with_spans(definition, 1).unwrap();
+    let result = forth.process_instructions_with_spans(instructions, spans);
+    assert_eq!(result, Ok(()));
+    let (instructions, spans) = forth.parse_with_spans(input, 3).unwrap();
+    let result = forth.process_instructions_with_spans(instructions, spans);
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(forth.get_stack_content(), &vec![6]);
+    assert_eq!(
+        forth.stack_effect(&WordType::UserDefined("double".to_string())),
+        Some("n -- 2n")
+    );
+}
+
+#[test]
+fn strings_are_stored_once_when_a_word_is_defined() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+    let definition = r#": greet .( compiling) s" hello" type ;"#.to_string();
+    let input = r#"here greet greet here - c" hi" count type"#.to_string();
+
+    let instructions = forth.parse_instructions(definition);
+    let definition_result = forth.process_instructions(instructions);
+    let instructions = forth.parse_instructions(input);
+    let result = forth.process_instructions(instructions);
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(definition_result, Ok(()));
+    assert_eq!(result, Ok(()));
+    assert_eq!(output, "compilinghellohellohi");
+    assert_eq!(forth.get_stack_content(), &vec![0]);
+}
+
+#[test]
+fn string_escapes_and_utf8_text_are_printed_as_written() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+    let input = r#"S\"Tab\tA\x41\"" TYPE ."Ñandú""#.to_string();
+
+    let instructions = forth.parse_instructions(input);
+    let result = forth.process_instructions(instructions);
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(result, Ok(()));
+    assert_eq!(output, "Tab\tAA\"Ñandú ");
+}
+
+#[test]
+fn words_and_see_show_the_names_as_they_were_defined() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+    let definitions = [": Square ( n -- n*n ) DUP * ;", ": Cube dup Square * ;"];
+    let input = "3 CUBE words SEE cube".to_string();
+
+    for (line, definition) in definitions.into_iter().enumerate() {
+        let (instructions, spans) = forth
+            .parse_with_spans(definition.to_string(), line)
+            .unwrap();
+        let result = forth.process_instructions_with_spans(instructions, spans);
+        assert_eq!(result, Ok(()));
+    }
+    let (instructions, spans) = forth.parse_with_spans(input, 3).unwrap();
+    let result = forth.process_instructions_with_spans(instructions, spans);
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(result, Ok(()));
+    assert_eq!(output, "Cube Square\n: Cube dup Square * ;\n");
+    assert_eq!(forth.get_stack_content(), &vec![27]);
+}
+
+#[test]
+fn case_sensitive_words_are_told_apart() {
+    let mut forth: Forth<Sink> =
+        Forth::new(None, None).with_case_sensitivity(CaseSensitivity::Sensitive);
+    let input = [": one 1 ;", ": ONE 10 ;", "one ONE"];
+
+    for line in input {
+        let instructions = forth.parse_instructions(line.to_string());
+        let result = forth.process_instructions(instructions);
+        assert_eq!(result, Ok(()));
+    }
+
+    assert_eq!(forth.get_stack_content(), &vec![1, 10]);
+}
+
+#[test]
+fn forget_restores_the_dictionary_and_the_data_space() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let lines = [
+        ": foo 1 ;",
+        "here variable counter : foo 2 ;",
+        ": greet s\" hello\" ;",
+        "forget counter here foo",
+    ];
+
+    for line in lines {
+        let instructions = forth.parse_instructions(line.to_string());
+        let result = forth.process_instructions(instructions);
+        assert_eq!(result, Ok(()));
+    }
+
+    let here = forth.get_stack_content()[0];
+    assert_eq!(forth.get_stack_content(), &vec![here, here, 1]);
+    assert!(!forth.is_word_defined(&WordType::UserDefined("greet".to_string())));
+}
+
+#[test]
+fn markers_forget_themselves_and_the_words_defined_after_them() {
+    let mut forth: Forth<Sink> = Forth::new(None, None);
+    let lines = [
+        ": keep 1 ;",
+        "marker -work- here",
+        "variable scratch 100 allot",
+        ": keep 2 ;",
+        "-work- here keep",
+    ];
+
+    for line in lines {
+        let instructions = forth.parse_instructions(line.to_string());
+        let result = forth.process_instructions(instructions);
+        assert_eq!(result, Ok(()));
+    }
+
+    let here = forth.get_stack_content()[0];
+    assert_eq!(forth.get_stack_content(), &vec![here, here, 1]);
+    assert!(!forth.is_word_defined(&WordType::UserDefined("-work-".to_string())));
+    assert!(!forth.is_word_defined(&WordType::UserDefined("scratch".to_string())));
+}
+
+#[test]
+fn redefining_a_word_writes_a_warning() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+
+    for line in [": foo 1 ;", ": FOO 2 ;", "3 constant foo", "variable bar"] {
+        let instructions = forth.parse_instructions(line.to_string());
+        let result = forth.process_instructions(instructions);
+        assert_eq!(result, Ok(()));
+    }
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(output, "redefined FOO\nredefined foo\n");
+}
+
+#[test]
+fn undefined_words_inside_a_definition_are_not_reported_in_the_program_output() {
+    let output = Vec::new();
+    let mut forth = Forth::new(None, Some(output));
+
+    for line in [": double 2 * ;", ": quad doubel double ;", "1 quad ."] {
+        let instructions = forth.parse_instructions(line.to_string());
+        let result = forth.process_instructions(instructions);
+        assert_eq!(result, Ok(()));
+    }
+
+    let output = String::from_utf8(forth.get_writer().unwrap().to_vec()).unwrap();
+    assert_eq!(output, "2 ");
+}